@@ -2,7 +2,7 @@
     Bench the individual algorithm for each line type (Line, Quad, Cube)
 */
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use easy_signed_distance_field::Font;
+use easy_signed_distance_field::{Font, SdfSettings};
 
 pub fn bench_font(c: &mut Criterion) {
     use std::fs;
@@ -26,6 +26,12 @@ pub fn bench_font(c: &mut Criterion) {
         }
     ));
 
+    let alphabet: Vec<char> = ('A'..='Z').chain('a'..='z').collect();
+    let settings = SdfSettings { threads: 0, ..Default::default() };
+    c.bench_function("alphabet 64px batch", |b| b.iter(|| 
+        font.sdf_generate_batch(64.0, 2, 6.0, black_box(&alphabet), settings)
+    ));
+
 }
 
 criterion_group!(benches, bench_font);
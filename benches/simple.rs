@@ -2,7 +2,7 @@
     Bench the individual algorithm for each line type (Line, Quad, Cube)
 */
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use easy_signed_distance_field::{sdf_generate, Line, vec2};
+use easy_signed_distance_field::{sdf_generate, Line, PreparedShape, vec2};
 
 pub fn bench_line(c: &mut Criterion) {
     // Simple lines
@@ -64,6 +64,54 @@ pub fn bench_line(c: &mut Criterion) {
             black_box(&lines),
         )
     ));
+
+    // Cubic
+    let lines = [
+        Line::Curve { start: vec2(0.1, 0.5), end: vec2(0.9, 0.5), first_control: vec2(0.3, 0.0), second_control: vec2(0.7, 0.0) },
+        Line::Curve { start: vec2(0.9, 0.5), end: vec2(0.1, 0.5), first_control: vec2(0.7, 1.0), second_control: vec2(0.3, 1.0) },
+    ];
+
+    c.bench_function("cubic 64px", |b| b.iter(|| 
+        sdf_generate(
+            64,
+            64,
+            0,
+            5.0,
+            black_box(&lines),
+        )
+    ));
+
+    // Complex shape
+    let points = 200;
+    let star_point = |i: usize| {
+        let angle = (i as f32 / points as f32) * std::f32::consts::TAU;
+        let radius = if i.is_multiple_of(2) { 0.45 } else { 0.35 };
+        vec2(0.5 + angle.cos() * radius, 0.5 + angle.sin() * radius)
+    };
+
+    let lines: Vec<Line> = (0..points)
+        .map(|i| Line::Line { start: star_point(i), end: star_point((i + 1) % points) })
+        .collect();
+
+    c.bench_function("star 64px", |b| b.iter(|| 
+        sdf_generate(
+            64,
+            64,
+            0,
+            15.0,
+            black_box(&lines),
+        )
+    ));
+
+    let shape = PreparedShape::new(&lines);
+    c.bench_function("prepared star 64px", |b| b.iter(|| 
+        black_box(&shape).sdf_generate(
+            64,
+            64,
+            0,
+            15.0,
+        )
+    ));
 }
 
 criterion_group!(benches, bench_line);
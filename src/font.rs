@@ -1,7 +1,7 @@
 use ttf_parser::{Face, FaceParsingError, name_id::FULL_NAME};
 use std::{ops::Deref, collections::HashMap};
 
-use super::{font_geometry::{FontGeometry, OutlineBounds}, Line, SdfRaster, sdf_generate};
+use super::{font_geometry::{FontGeometry, OutlineBounds}, math::{Vec2, vec2}, Line, SdfRaster, SdfSettings, SdfTarget, SdfValue, Spread, sdf_generate_into, sdf_generate_with_settings, msdf_generate, mtsdf_generate, thread_count};
 
 
 /// Settings for controlling specific font and layout behavior.
@@ -71,7 +71,42 @@ pub struct Metrics {
     /// Advance width of the glyph in subpixels. Used in horizontal fonts.
     pub advance_width: f32,
     /// The bounding box that contains the glyph's outline at the offsets specified by the font.
-    pub bounds: OutlineBounds
+    pub bounds: OutlineBounds,
+    /// The distance range of the sdf in pixels, see [SdfRaster::pixel_range]. Zero if the metrics are not returned with a sdf (ex: [Font::metrics]).
+    pub pixel_range: Vec2,
+    /// The distance range of the sdf in em units. A shader can use it to decode the distances at any font size.
+    /// 
+    /// The padding shrinks the glyph in the raster, so a pixel covers more than `1 / px` em, and not the same amount on both axes.
+    pub em_range: Vec2,
+}
+
+impl Metrics {
+    fn with_range(self, pixel_range: Vec2, padding: i32, px: f32) -> Metrics {
+        Metrics { pixel_range, em_range: pixel_range * self.pixel_em_size(padding, px), ..self }
+    }
+
+    /// Return the size (in em units) of a pixel of the sdf of the glyph rendered with `padding`
+    fn pixel_em_size(&self, padding: i32, px: f32) -> Vec2 {
+        // The raster size is rounded down from the bounds, and the padding shrinks the glyph in the raster
+        let axis = |size: i32, bounds: f32| match size > 0 {
+            true => (1.0 + ((2 * padding) as f32 / size as f32)) * (bounds / size as f32) / px,
+            false => 1.0 / px,
+        };
+
+        vec2(axis(self.width, self.bounds.width), axis(self.height, self.bounds.height))
+    }
+
+    /// Convert a [Spread::Em] to a [Spread::Pixels] using the em size of the glyph pixels.
+    /// A spread only has one range, so the range is averaged between the two axes. [Metrics::em_range] reports the exact ranges.
+    fn resolve_spread(&self, spread: Spread, padding: i32, px: f32) -> Spread {
+        match spread {
+            Spread::Em { range, .. } => {
+                let size = self.pixel_em_size(padding, px);
+                Spread::Pixels(range * 2.0 / (size[0] + size[1]))
+            },
+            spread => spread,
+        }
+    }
 }
 
 
@@ -223,6 +258,7 @@ impl Font {
             height: bounds.height as i32,
             advance_width: glyph.advance_width * scale,
             bounds: bounds,
+            ..Default::default()
         };
 
         Some(metrics)
@@ -240,13 +276,14 @@ impl Font {
     }
 
     /// Generates the sdf for the character `c`. The font instance scale will be used for the output size.
-    /// Use [sdf_generate] under the hood.
+    /// Use [sdf_generate](crate::sdf_generate) under the hood.
     /// 
     /// # Arguments
     ///
     /// * `px` - The size to scale the glyph by. Th e units of the scale are pixels per Em unit.
     /// * `padding` - Padding (in px) to add around the glyph. Should be > 0
-    /// * `spread` - Control how the gradient in the sdf spread. 
+    /// * `spread` - Control how the gradient in the sdf spread. Use [Spread::em] to set the distance range in em units,
+    ///   so that the range is the same for every `px` and `padding`. The ranges of the output are reported in [Metrics::pixel_range] and [Metrics::em_range].
     /// * `c` - Character to render
     /// 
     /// # Return
@@ -260,7 +297,29 @@ impl Font {
     /// # Panics
     /// 
     /// Panics if `px` is smaller than 1.0
-    pub fn sdf_generate(&self, px: f32, padding: i32, spread: f32, c: char) -> Option<(Metrics, SdfRaster)> {
+    pub fn sdf_generate(&self, px: f32, padding: i32, spread: impl Into<Spread>, c: char) -> Option<(Metrics, SdfRaster)> {
+        self.sdf_generate_with_settings(px, padding, spread, c, SdfSettings::default())
+    }
+
+    /// Same as [Font::sdf_generate], but with extra [SdfSettings] to control the generation.
+    /// Use [sdf_generate_with_settings] under the hood.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `px` is smaller than 1.0
+    /// 
+    /// # Usage
+    /// 
+    /// ```rust
+    /// use easy_signed_distance_field as sdf;
+    /// 
+    /// /// Variable fonts can have overlapping contours, that would be rendered as holes with the default fill rule
+    /// fn render_variable_char(font: &sdf::Font, c: char) -> Option<(sdf::Metrics, sdf::SdfRaster)> {
+    ///     let settings = sdf::SdfSettings { fill_rule: sdf::FillRule::NonZero, ..Default::default() };
+    ///     font.sdf_generate_with_settings(64.0, 2, 6.0, c, settings)
+    /// }
+    /// ```
+    pub fn sdf_generate_with_settings(&self, px: f32, padding: i32, spread: impl Into<Spread>, c: char, settings: SdfSettings) -> Option<(Metrics, SdfRaster)> {
         if px < 1.0 {
             panic!("Sdf render size cannot be smaller than 1.0 (got {:?})", px);
         }
@@ -274,9 +333,158 @@ impl Font {
 
         //println!("{:?} {:?}", metrics.width, metrics.height);
 
-        let sdf = sdf_generate(metrics.width as u32, metrics.height as u32, padding, spread, &glyph.lines);
+        let spread = metrics.resolve_spread(spread.into(), padding, px);
+        let sdf = sdf_generate_with_settings(metrics.width as u32, metrics.height as u32, padding, spread, &glyph.lines, settings);
+
+        Some((metrics.with_range(sdf.pixel_range, padding, px), sdf))
+    }
+
+    /// Same as [Font::sdf_generate_with_settings], but writes the sdf in a sub-rectangle of an existing buffer (ex: an atlas page)
+    /// instead of allocating a new raster. Use [sdf_generate_into] under the hood.
+    /// 
+    /// The size of the rectangle is `metrics.width`x`metrics.height`, use [Font::metrics] to find it before rendering the glyph.
+    /// 
+    /// # Return
+    /// 
+    /// Returns `Some(Metrics)` if the render was successful, and `None` if `c` is not a character in the font face.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `px` is smaller than 1.0, or if the glyph does not fit in `target`
+    /// 
+    /// # Usage
+    /// 
+    /// ```rust
+    /// use easy_signed_distance_field as sdf;
+    /// 
+    /// /// Render `c` at the position `(x, y)` of a 512x512 atlas. Returns the metrics of the glyph.
+    /// fn render_in_atlas(font: &sdf::Font, atlas: &mut [u8], c: char, x: u32, y: u32) -> Option<sdf::Metrics> {
+    ///     let target = sdf::SdfTarget { buffer: atlas, stride: 512, x, y };
+    ///     font.sdf_generate_into(32.0, 2, 6.0, c, Default::default(), target)
+    /// }
+    /// ```
+    pub fn sdf_generate_into<T: SdfValue>(&self, px: f32, padding: i32, spread: impl Into<Spread>, c: char, settings: SdfSettings, target: SdfTarget<'_, T>) -> Option<Metrics> {
+        if px < 1.0 {
+            panic!("Sdf render size cannot be smaller than 1.0 (got {:?})", px);
+        }
+
+        let glyph = self.glyphs.get(&c)?;
+        let metrics = self.metrics(c, px).unwrap(); // Cannot return `None` if glyph is some
+        let spread = metrics.resolve_spread(spread.into(), padding, px);
+        let pixel_range = sdf_generate_into(metrics.width as u32, metrics.height as u32, padding, spread, &glyph.lines, settings, target);
+
+        Some(metrics.with_range(pixel_range, padding, px))
+    }
+
+    /// Generates the sdf of every character in `chars`. The characters are split between `settings.threads` threads,
+    /// and each sdf is identical to the one returned by [Font::sdf_generate_with_settings].
+    /// 
+    /// # Arguments
+    ///
+    /// * `px`, `padding`, `spread`: Same as [Font::sdf_generate]
+    /// * `chars` - Characters to render
+    /// * `settings` - The generation settings. See [SdfSettings]
+    /// 
+    /// # Return
+    /// 
+    /// Returns one item per character in `chars`, in the same order. An item is `None` if the character is not in the font face.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `px` is smaller than 1.0
+    /// 
+    /// # Usage
+    /// 
+    /// ```rust
+    /// use easy_signed_distance_field as sdf;
+    /// 
+    /// /// Renders the lower case alphabet using all the available threads
+    /// fn render_alphabet(font: &sdf::Font) -> Vec<Option<(sdf::Metrics, sdf::SdfRaster)>> {
+    ///     let chars: Vec<char> = ('a'..='z').collect();
+    ///     let settings = sdf::SdfSettings { threads: 0, ..Default::default() };
+    ///     font.sdf_generate_batch(64.0, 2, 6.0, &chars, settings)
+    /// }
+    /// ```
+    pub fn sdf_generate_batch(&self, px: f32, padding: i32, spread: impl Into<Spread>, chars: &[char], settings: SdfSettings) -> Vec<Option<(Metrics, SdfRaster)>> {
+        if px < 1.0 {
+            panic!("Sdf render size cannot be smaller than 1.0 (got {:?})", px);
+        }
+
+        // Each glyph is generated on a single thread
+        let spread = spread.into();
+        let glyph_settings = SdfSettings { threads: 1, ..settings };
+
+        let mut output: Vec<Option<(Metrics, SdfRaster)>> = chars.iter().map(|_| None).collect();
+        let threads = thread_count(settings.threads).min(chars.len());
+        if threads <= 1 {
+            for (out, &c) in output.iter_mut().zip(chars.iter()) {
+                *out = self.sdf_generate_with_settings(px, padding, spread, c, glyph_settings);
+            }
+            return output;
+        }
+
+        let chars_per_thread = chars.len().div_ceil(threads);
+        std::thread::scope(|scope| {
+            for (outputs, chars) in output.chunks_mut(chars_per_thread).zip(chars.chunks(chars_per_thread)) {
+                scope.spawn(move || {
+                    for (out, &c) in outputs.iter_mut().zip(chars.iter()) {
+                        *out = self.sdf_generate_with_settings(px, padding, spread, c, glyph_settings);
+                    }
+                });
+            }
+        });
+
+        output
+    }
+
+    /// Generates the multi-channel sdf (msdf) for the character `c`. The font instance scale will be used for the output size.
+    /// Use [msdf_generate] under the hood. Arguments are the same as [Font::sdf_generate].
+    /// 
+    /// # Return
+    /// 
+    /// Returns `Some([Metrics], [SdfRaster])` with a 3 channels raster if the render was successful
+    /// 
+    /// Returns `None` if `c` is not a character in the font face.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `px` is smaller than 1.0
+    pub fn msdf_generate(&self, px: f32, padding: i32, spread: impl Into<Spread>, c: char) -> Option<(Metrics, SdfRaster)> {
+        if px < 1.0 {
+            panic!("Sdf render size cannot be smaller than 1.0 (got {:?})", px);
+        }
+
+        let glyph = self.glyphs.get(&c)?;
+        let metrics = self.metrics(c, px).unwrap(); // Cannot return `None` if glyph is some
+        let spread = metrics.resolve_spread(spread.into(), padding, px);
+        let sdf = msdf_generate(metrics.width as u32, metrics.height as u32, padding, spread, &glyph.lines);
+
+        Some((metrics.with_range(sdf.pixel_range, padding, px), sdf))
+    }
+
+    /// Generates the multi-channel and true sdf (mtsdf) for the character `c`. The font instance scale will be used for the output size.
+    /// Use [mtsdf_generate] under the hood. Arguments are the same as [Font::sdf_generate].
+    /// 
+    /// # Return
+    /// 
+    /// Returns `Some([Metrics], [SdfRaster])` with a 4 channels raster if the render was successful
+    /// 
+    /// Returns `None` if `c` is not a character in the font face.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `px` is smaller than 1.0
+    pub fn mtsdf_generate(&self, px: f32, padding: i32, spread: impl Into<Spread>, c: char) -> Option<(Metrics, SdfRaster)> {
+        if px < 1.0 {
+            panic!("Sdf render size cannot be smaller than 1.0 (got {:?})", px);
+        }
+
+        let glyph = self.glyphs.get(&c)?;
+        let metrics = self.metrics(c, px).unwrap(); // Cannot return `None` if glyph is some
+        let spread = metrics.resolve_spread(spread.into(), padding, px);
+        let sdf = mtsdf_generate(metrics.width as u32, metrics.height as u32, padding, spread, &glyph.lines);
 
-        Some((metrics, sdf))
+        Some((metrics.with_range(sdf.pixel_range, padding, px), sdf))
     }
 
     fn scale_factor(&self, px: f32) -> f32 {
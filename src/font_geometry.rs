@@ -1,5 +1,5 @@
 use ttf_parser::OutlineBuilder;
-use super::{Line, math::vec2};
+use super::{Line, math::vec2, shape::lines_bounds};
 
 #[derive(Default, Copy, Clone)]
 struct Point {
@@ -60,45 +60,22 @@ impl FontGeometry {
     }
 
     pub fn finalize(&mut self) {
-        // Compute bounds
-        let mut xmin = f32::INFINITY;
-        let mut xmax = f32::NEG_INFINITY;
-        let mut ymin = f32::INFINITY;
-        let mut ymax = f32::NEG_INFINITY;
-        for line in self.lines.iter() {
-            let [x0, y0, x1, y1] = match line {
-                Line::Line { start, end } | Line::Quad { start, end, .. } | Line::Curve { start, end, ..} => {
-                    [start[0], start[1], end[0], end[1]]
-                }
-            };
-
-            if x0 < xmin { xmin = x0; }
-            if x1 < xmin { xmin = x1; }
-            if x0 > xmax { xmax = x0; }
-            if x1 > xmax { xmax = x1; }
-
-            if y0 < ymin { ymin = y0; }
-            if y1 < ymin { ymin = y1; }
-            if y0 > ymax { ymax = y0; }
-            if y1 > ymax { ymax = y1; }
-        }
-
-        if xmin == f32::INFINITY || ymin == f32::INFINITY {
-            self.bounds = OutlineBounds {
+        // Compute bounds, including the extrema of the curves
+        self.bounds = match lines_bounds(&self.lines) {
+            Some((min, max)) => OutlineBounds {
+                xmin: min[0],
+                ymin: min[1],
+                width: max[0] - min[0],
+                height: max[1] - min[1],
+            },
+            None => OutlineBounds {
                 xmin: 0.0,
                 ymin: 0.0,
                 width: 0.0,
                 height: 0.0,
-            };
-        } else {
-            self.bounds = OutlineBounds {
-                xmin,
-                ymin,
-                width: xmax - xmin,
-                height: ymax - ymin,
-            };
-        }
-       
+            },
+        };
+
         // Normalize lines
         let b = self.bounds;
         for line in self.lines.iter_mut() {
@@ -1,14 +1,40 @@
+use std::borrow::Cow;
+
 mod math;
 pub use math::{vec2, Vec2};
 
 mod line;
-pub use line::Line;
+pub use line::{ClosestPoint, Line};
+
+mod shape;
+pub use shape::{Contour, Shape, flatten_lines, lines_bounds, reverse_lines, signed_area};
+
+pub mod shapes;
+
+mod msdf;
+
+mod prepared;
+pub use prepared::PreparedShape;
+
+mod mask;
+pub use mask::*;
+
+mod stroke;
+pub use stroke::*;
+
+mod validate;
+pub use validate::*;
 
 #[cfg(feature="path")]
 pub(crate) mod path;
 #[cfg(feature="path")]
 pub use path::*;
 
+#[cfg(feature="svg")]
+pub(crate) mod svg;
+#[cfg(feature="svg")]
+pub use svg::*;
+
 #[cfg(feature="font")]
 pub(crate) mod font_geometry;
 
@@ -17,15 +43,24 @@ mod font;
 #[cfg(feature="font")]
 pub use font::*;
 
-/// SDF output of a shape by [sdf_raster]
+/// SDF output of a shape by [sdf_generate], [msdf_generate] or [mtsdf_generate]
 pub struct SdfRaster {
     /// Width of the buffer in pixel
     pub width: u32,
     /// Height of the buffer in pixel
     pub height: u32,
+    /// Number of values per pixel. `1` for a sdf, `3` (red, green, blue) for a msdf, `4` (red, green, blue, alpha) for a mtsdf
+    pub channels: u32,
     /// Buffer data. Each values represent the distance of the pixel to the nearest line
     /// Values range from 0.0 (outside) to 1.0 (inside) with 0.5 being directly on a line
-    pub buffer: Vec<f32>
+    /// 
+    /// Multi channel pixels are stored next to each other (ex: `[r0, g0, b0, r1, g1, b1, ...]`)
+    pub buffer: Vec<f32>,
+    /// Distance (in pixels of the raster, on the x and y axis) covered by the values going from 0.0 to 1.0.
+    /// A shader needs this value to convert the sdf back to a distance: `distance_in_pixels = (value - 0.5) * pixel_range`.
+    /// 
+    /// When the sdf is drawn at a different size, the range on screen is `pixel_range * (screen_size / raster_size)`.
+    pub pixel_range: Vec2,
 }
 
 /// SDF output of a shape converted to an array of bytes using [sdf_to_bitmap].
@@ -34,9 +69,167 @@ pub struct SdfBitmap {
     pub width: u32,
     /// Height of the buffer in pixel
     pub height: u32,
+    /// Number of values per pixel. Same as the source [SdfRaster]
+    pub channels: u32,
     /// Buffer data. Each values represent the distance of the pixel to the nearest line
     /// Values range from 0 (outside) to 255 (inside) with 127 being directly on a line
-    pub buffer: Vec<u8>
+    pub buffer: Vec<u8>,
+    /// Same as the source [SdfRaster]
+    pub pixel_range: Vec2,
+}
+
+/// Signed distances of a shape by [signed_distance_generate]. Unlike [SdfRaster], the distances are not scaled nor clamped.
+pub struct SignedDistanceRaster {
+    /// Width of the buffer in pixel
+    pub width: u32,
+    /// Height of the buffer in pixel
+    pub height: u32,
+    /// Units of the distances in the buffer
+    pub units: DistanceUnits,
+    /// Buffer data. Each values is the distance of the pixel to the nearest line. 
+    /// Negative values are outside the shape, positive values are inside the shape.
+    pub buffer: Vec<f32>,
+}
+
+/// A sub-rectangle of a caller-provided buffer, where [sdf_generate_into] writes a sdf.
+/// 
+/// The pixel `(x, y)` of the sdf is written at `buffer[((self.y + y) * stride) + self.x + x]`.
+pub struct SdfTarget<'a, T: SdfValue> {
+    /// Buffer receiving the sdf. Usually a whole atlas page.
+    pub buffer: &'a mut [T],
+    /// Number of values between the start of two rows of `buffer`. Usually the width of the atlas page, or the width aligned for a gpu upload.
+    pub stride: usize,
+    /// Position of the left column of the sdf in `buffer`
+    pub x: u32,
+    /// Position of the top row of the sdf in `buffer`
+    pub y: u32,
+}
+
+/// A rectangle of pixels in a raster. See [sdf_generate_region] and [sdf_regenerate_region]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SdfRegion {
+    /// Left column of the rectangle
+    pub x: u32,
+    /// Top row of the rectangle
+    pub y: u32,
+    /// Width of the rectangle in pixel
+    pub width: u32,
+    /// Height of the rectangle in pixel
+    pub height: u32,
+}
+
+/// Type of the values that can be written by [sdf_generate_into]
+pub trait SdfValue: Copy + Send {
+    /// Convert a sdf value going from 0.0 (outside) to 1.0 (inside)
+    fn from_sdf(value: f32) -> Self;
+}
+
+impl SdfValue for f32 {
+    fn from_sdf(value: f32) -> Self {
+        value
+    }
+}
+
+impl SdfValue for u8 {
+    /// Same conversion as [sdf_to_bitmap]
+    fn from_sdf(value: f32) -> Self {
+        (value * 255.0) as u8
+    }
+}
+
+/// Settings for controlling how a sdf is generated. See [sdf_generate_with_settings]
+#[derive(Copy, Clone, Debug)]
+pub struct SdfSettings {
+    /// The default is 1. Number of threads used to generate the sdf. The rows of the raster are split between the threads
+    /// and the output is identical to a single threaded generation. If `0`, the number of threads available on the system is used.
+    pub threads: usize,
+    /// The default is [FillRule::EvenOdd]. Rule used to decide if a pixel is inside or outside the shape.
+    pub fill_rule: FillRule,
+    /// The default is [DistanceUnits::Normalized]. Units of the distances scaled by `spread`.
+    pub units: DistanceUnits,
+}
+
+impl Default for SdfSettings {
+    fn default() -> Self {
+        SdfSettings { threads: 1, fill_rule: FillRule::EvenOdd, units: DistanceUnits::Normalized }
+    }
+}
+
+/// Units of the distances scaled by `spread` when generating a sdf.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DistanceUnits {
+    /// Distances are measured in the normalized space of the raster, where the raster goes from `0.0` to `1.0` on both axis.
+    /// On a raster that is not square, the gradient is stretched, and the gradient width in pixels changes with the size of the raster.
+    #[default]
+    Normalized,
+    /// Distances are measured in pixels of the output raster. The gradient is the same on both axis and a `spread` gives the
+    /// same gradient width (in pixels) at any raster size: values go from 0.5 on the edge to 0.0 (outside) or 1.0 (inside) 
+    /// at `0.5 / spread` pixels from the edge.
+    Pixels,
+}
+
+/// Rule used to decide if a point is inside or outside a shape. Both rules give the same result unless contours overlap.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FillRule {
+    /// A point is inside the shape if an horizontal ray starting at the point crosses the lines an odd number of times.
+    /// Overlapping contours create holes.
+    #[default]
+    EvenOdd,
+    /// A point is inside the shape if the lines going up and the lines going down crossed by an horizontal ray starting at the point
+    /// do not cancel each other. Overlapping contours with the same direction are merged.
+    NonZero,
+}
+
+/// Distance range covered by the values going from 0.0 (outside) to 1.0 (inside) in a sdf.
+/// 
+/// Every function accepting a `spread` also accepts a `f32`, which is the same as [Spread::Factor].
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+/// 
+/// /// The values go from 0.5 on the edges to 0.0 or 1.0 at 4 pixels from the edges
+/// fn generate_8px_range(lines: &[sdf::Line]) -> sdf::SdfRaster {
+///     let sdf = sdf::sdf_generate(64, 64, 2, sdf::Spread::Pixels(8.0), lines);
+///     assert_eq!(sdf.pixel_range, sdf::vec2(8.0, 8.0));
+///     sdf
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Spread {
+    /// Unitless multiplier of the distances. A higher value means less spread. The distances are measured in [SdfSettings::units].
+    Factor(f32),
+    /// Total distance range, in pixels of the output raster. The values go from 0.5 on the edges to 0.0 (outside) or 1.0 (inside)
+    /// at `range / 2.0` pixels from the edges. The distances are always measured in [DistanceUnits::Pixels].
+    Pixels(f32),
+    /// Total distance range in em units, for a font glyph rendered at `px` pixels per em. See [Spread::em].
+    Em { range: f32, px: f32 },
+}
+
+impl Spread {
+    /// Total distance range in em units, for a font glyph rendered at `px` pixels per em.
+    /// 
+    /// The `Font` methods convert the range with the em size of the glyph pixels, which accounts for the padding.
+    /// The other functions have no glyph and use `Spread::Pixels(range * px)`, which is only exact without padding.
+    pub fn em(range: f32, px: f32) -> Spread {
+        Spread::Em { range, px }
+    }
+
+    /// Return the spread factor and the units of the distances
+    fn resolve(self, units: DistanceUnits) -> (f32, DistanceUnits) {
+        match self {
+            Spread::Factor(spread) => (spread, units),
+            Spread::Pixels(range) => (1.0 / range, DistanceUnits::Pixels),
+            Spread::Em { range, px } => (1.0 / (range * px), DistanceUnits::Pixels),
+        }
+    }
+}
+
+impl From<f32> for Spread {
+    fn from(spread: f32) -> Self {
+        Spread::Factor(spread)
+    }
 }
 
 /// Rasterize a shape defined by `lines`
@@ -45,7 +238,8 @@ pub struct SdfBitmap {
 /// * `width`: Width (in pixels) of the output raster
 /// * `height`: Height (in pixels) of the output raster
 /// * `padding`: Padding added to the output raster, this won't change the bitmap size, but it will reduce the sdf quality as the work area will be smaller
-/// * `spread`: Control how the gradient in the sdf spread. A higher value means less spread. 15.0 is a good default value
+/// * `spread`: Control how the gradient in the sdf spread. A higher value means less spread. 15.0 is a good default value.
+///   Can also be a [Spread] to set the distance range in pixels.
 /// For best result the outer borders of your sdf should be pure black and the inner part of the shape should be pure white.
 /// * `lines`: List of [line::Line] to be rasterized. Assumes the shape is closed and that the lines coordinates are normalized (aka between 0.0 and 1.0).
 /// 
@@ -58,74 +252,509 @@ pub fn sdf_generate(
     width: u32,
     height: u32,
     padding: i32,
-    spread: f32,
+    spread: impl Into<Spread>,
     lines: &[line::Line]
 ) -> SdfRaster {
-    let mut lines = lines;
-    let mut padded_lines: Vec<line::Line> = Vec::with_capacity(lines.len());
-    if padding != 0 {
-        let padding_width_normalized = padding as f32 / width as f32;
-        let padding_height_normalized = padding as f32 / height as f32;
-        for line in lines.iter() {
-            padded_lines.push(line.normalize_to_with_offset(
-                -padding_width_normalized,
-                -padding_height_normalized,
-                1.0 as f32 + (padding_width_normalized * 2.0),
-                1.0 as f32 + (padding_height_normalized * 2.0)
-            ));
-        }
+    sdf_generate_with_settings(width, height, padding, spread, lines, SdfSettings::default())
+}
 
-        lines = padded_lines.as_slice();
-    }
+/// Same as [sdf_generate], but with extra [SdfSettings] to control the generation.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+/// 
+/// /// Splits the work between 4 threads. The output is the same as `sdf::sdf_generate`
+/// fn generate_threaded(lines: &[sdf::Line]) -> sdf::SdfRaster {
+///     let settings = sdf::SdfSettings { threads: 4, ..Default::default() };
+///     sdf::sdf_generate_with_settings(64, 64, 2, 8.0, lines, settings)
+/// }
+/// 
+/// /// The gradient is 8 pixels wide on each side of the edges, for any raster size
+/// fn generate_pixels(lines: &[sdf::Line], size: u32) -> sdf::SdfRaster {
+///     let settings = sdf::SdfSettings { units: sdf::DistanceUnits::Pixels, ..Default::default() };
+///     sdf::sdf_generate_with_settings(size, size, 2, 0.5 / 8.0, lines, settings)
+/// }
+/// 
+/// /// Overlapping contours are merged instead of creating holes
+/// fn generate_non_zero(lines: &[sdf::Line]) -> sdf::SdfRaster {
+///     let settings = sdf::SdfSettings { fill_rule: sdf::FillRule::NonZero, ..Default::default() };
+///     sdf::sdf_generate_with_settings(64, 64, 2, 8.0, lines, settings)
+/// }
+/// ```
+pub fn sdf_generate_with_settings(
+    width: u32,
+    height: u32,
+    padding: i32,
+    spread: impl Into<Spread>,
+    lines: &[line::Line],
+    settings: SdfSettings,
+) -> SdfRaster {
+    let (spread, units) = spread.into().resolve(settings.units);
+    let settings = SdfSettings { units, ..settings };
+    let padded_lines = distance_space_lines(width, height, settings.units, pad_lines(width, height, padding, lines));
+    let lines: &[line::Line] = &padded_lines;
 
-    let _1w = 1.0 / width as f32;
-    let _1h = 1.0 / height as f32;
-    
     let buffer_size = (width * height) as usize;
     let mut image_buffer: Vec<f32> = vec![0.0; buffer_size];
-    
-    // Compute the distance between lines
-    for x in 0..width {
-        for y in 0..height {
-            let px = (x as f32 + 0.5) * _1w;
-            let py = (y as f32 + 0.5) * _1h;
-            let index = (x + (width * y)) as usize;
-
-            let mut min_distance = f32::MAX;
-            for line in lines {
-                let d = line.distance(px, py);
-                if d < min_distance {
-                    min_distance = d;
-                }
-            }
 
-            min_distance = (1.0 - (min_distance * spread)) - 0.5;
-            image_buffer[index] = min_distance.clamp(0.0, 1.0);
-        }
+    let pixel = pixel_size(width, height, settings.units);
+    for_each_row(&mut image_buffer, width as usize, settings.threads, |y, row| {
+        sdf_row(pixel, spread, lines, settings, 0, y, row);
+    });
+
+    SdfRaster {
+        width,
+        height,
+        channels: 1,
+        buffer: image_buffer,
+        pixel_range: pixel_range(width, height, spread, units),
+    }
+}
+
+/// Same as [sdf_generate_with_settings], but writes the sdf in a sub-rectangle of an existing buffer instead of allocating a new raster.
+/// 
+/// # Arguments
+/// 
+/// * `width`, `height`, `padding`, `spread`, `lines`, `settings`: Same as [sdf_generate_with_settings]
+/// * `target`: Where to write the sdf. The values outside of the `width`x`height` rectangle are not modified.
+/// 
+/// # Return
+/// 
+/// Returns the pixel range of the sdf. See [SdfRaster::pixel_range]
+/// 
+/// # Panics
+/// 
+/// Panics if the rectangle does not fit in `target`
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+/// 
+/// /// Render a 32x32 shape in the bottom right corner of a 256x256 atlas
+/// fn render_in_atlas(atlas: &mut [u8], lines: &[sdf::Line]) {
+///     let target = sdf::SdfTarget { buffer: atlas, stride: 256, x: 224, y: 224 };
+///     sdf::sdf_generate_into(32, 32, 2, 8.0, lines, Default::default(), target);
+/// }
+/// ```
+pub fn sdf_generate_into<T: SdfValue>(
+    width: u32,
+    height: u32,
+    padding: i32,
+    spread: impl Into<Spread>,
+    lines: &[line::Line],
+    settings: SdfSettings,
+    target: SdfTarget<'_, T>,
+) -> Vec2 {
+    let (spread, units) = spread.into().resolve(settings.units);
+    let settings = SdfSettings { units, ..settings };
+    let padded_lines = distance_space_lines(width, height, settings.units, pad_lines(width, height, padding, lines));
+    let region = SdfRegion { x: 0, y: 0, width, height };
+    sdf_region_into(width, height, spread, &padded_lines, settings, region, target);
+
+    pixel_range(width, height, spread, units)
+}
+
+/// Generates the pixels in `region` of the sdf of a `width`x`height` raster, without generating the rest of the raster.
+/// The output is identical to the same pixels of [sdf_generate_with_settings] with the same arguments.
+/// 
+/// This can be used to generate a sdf too large to be stored in memory, one tile at the time.
+/// 
+/// # Arguments
+/// 
+/// * `width`, `height`, `padding`, `spread`, `lines`, `settings`: Same as [sdf_generate_with_settings]
+/// * `region`: The pixels to generate
+/// 
+/// # Return
+/// 
+/// Returns a raster of `region.width`x`region.height` pixels. Its `pixel_range` is the same as the whole raster.
+/// 
+/// # Panics
+/// 
+/// Panics if `region` is not inside the raster
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+/// 
+/// /// Generate a 4096x4096 sdf in tiles of 256x256 pixels
+/// fn generate_tiles(lines: &[sdf::Line], mut save_tile: impl FnMut(sdf::SdfRegion, sdf::SdfRaster)) {
+///     for y in (0..4096).step_by(256) {
+///         for x in (0..4096).step_by(256) {
+///             let region = sdf::SdfRegion { x, y, width: 256, height: 256 };
+///             save_tile(region, sdf::sdf_generate_region(4096, 4096, 0, 200.0, lines, Default::default(), region));
+///         }
+///     }
+/// }
+/// ```
+pub fn sdf_generate_region(
+    width: u32,
+    height: u32,
+    padding: i32,
+    spread: impl Into<Spread>,
+    lines: &[line::Line],
+    settings: SdfSettings,
+    region: SdfRegion,
+) -> SdfRaster {
+    let (spread, units) = spread.into().resolve(settings.units);
+    let settings = SdfSettings { units, ..settings };
+    let padded_lines = distance_space_lines(width, height, settings.units, pad_lines(width, height, padding, lines));
+
+    let mut buffer: Vec<f32> = vec![0.0; (region.width * region.height) as usize];
+    let target = SdfTarget { buffer: &mut buffer, stride: region.width as usize, x: 0, y: 0 };
+    sdf_region_into(width, height, spread, &padded_lines, settings, region, target);
+
+    SdfRaster {
+        width: region.width,
+        height: region.height,
+        channels: 1,
+        buffer,
+        pixel_range: pixel_range(width, height, spread, units),
+    }
+}
+
+/// Regenerates the pixels in `region` of an existing sdf, generated by [sdf_generate_with_settings] with the same size, padding, spread and settings.
+/// The other pixels are not modified. Use [sdf_dirty_region] to find the pixels that changed when the lines of a shape are modified.
+/// 
+/// # Arguments
+/// 
+/// * `sdf`: The sdf to update. Must have a single channel.
+/// * `padding`, `spread`, `lines`, `settings`: Same as [sdf_generate_with_settings]
+/// * `region`: The pixels to regenerate
+/// 
+/// # Panics
+/// 
+/// Panics if `region` is not inside the raster, or if `sdf` has more than one channel
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+/// 
+/// /// Update `sdf` after the shape was modified from `old_lines` to `new_lines`
+/// fn update(sdf: &mut sdf::SdfRaster, old_lines: &[sdf::Line], new_lines: &[sdf::Line]) {
+///     let settings = sdf::SdfSettings::default();
+///     if let Some(region) = sdf::sdf_dirty_region(sdf.width, sdf.height, 2, 10.0, settings, old_lines, new_lines) {
+///         sdf::sdf_regenerate_region(sdf, 2, 10.0, new_lines, settings, region);
+///     }
+/// }
+/// ```
+pub fn sdf_regenerate_region(
+    sdf: &mut SdfRaster,
+    padding: i32,
+    spread: impl Into<Spread>,
+    lines: &[line::Line],
+    settings: SdfSettings,
+    region: SdfRegion,
+) {
+    if sdf.channels != 1 {
+        panic!("Only single channel sdf can be regenerated (got {:?} channels)", sdf.channels);
+    }
+
+    let (width, height) = (sdf.width, sdf.height);
+    let (spread, units) = spread.into().resolve(settings.units);
+    let settings = SdfSettings { units, ..settings };
+    let padded_lines = distance_space_lines(width, height, settings.units, pad_lines(width, height, padding, lines));
+
+    let target = SdfTarget { buffer: &mut sdf.buffer, stride: width as usize, x: region.x, y: region.y };
+    sdf_region_into(width, height, spread, &padded_lines, settings, region, target);
+}
+
+/// Return the pixels of a sdf that can change when the lines of a shape go from `old_lines` to `new_lines`.
+/// 
+/// The lines are compared by value and the order of the lines does not matter. The region covers the bounding box of the
+/// lines that were added or removed, extended by the distance where the sdf values are clamped (`0.5 / spread`).
+/// The pixels outside of this region keep the same value.
+/// 
+/// # Arguments
+/// 
+/// * `width`, `height`, `padding`, `spread`, `settings`: Same as [sdf_generate_with_settings]
+/// * `old_lines`: The lines used to generate the sdf
+/// * `new_lines`: The modified lines
+/// 
+/// # Return
+/// 
+/// Returns `None` if no pixels can change
+pub fn sdf_dirty_region(
+    width: u32,
+    height: u32,
+    padding: i32,
+    spread: impl Into<Spread>,
+    settings: SdfSettings,
+    old_lines: &[line::Line],
+    new_lines: &[line::Line],
+) -> Option<SdfRegion> {
+    let (spread, units) = spread.into().resolve(settings.units);
+
+    // Count the lines that are in one list but not in the other
+    let key = |line: &line::Line| match *line {
+        line::Line::Line { start, end } => [0, start[0].to_bits(), start[1].to_bits(), end[0].to_bits(), end[1].to_bits(), 0, 0, 0, 0],
+        line::Line::Quad { start, end, control } => [1, start[0].to_bits(), start[1].to_bits(), end[0].to_bits(), end[1].to_bits(), control[0].to_bits(), control[1].to_bits(), 0, 0],
+        line::Line::Curve { start, end, first_control, second_control } => [
+            2, start[0].to_bits(), start[1].to_bits(), end[0].to_bits(), end[1].to_bits(),
+            first_control[0].to_bits(), first_control[1].to_bits(), second_control[0].to_bits(), second_control[1].to_bits()
+        ],
+        line::Line::Arc { center, radius, rotation, start_angle, sweep_angle } => [
+            3, center[0].to_bits(), center[1].to_bits(), radius[0].to_bits(), radius[1].to_bits(),
+            rotation.to_bits(), start_angle.to_bits(), sweep_angle.to_bits(), 0
+        ],
+    };
+
+    let mut counts: std::collections::HashMap<[u32; 9], (i32, &line::Line)> = std::collections::HashMap::new();
+    for line in old_lines {
+        counts.entry(key(line)).or_insert((0, line)).0 += 1;
+    }
+    for line in new_lines {
+        counts.entry(key(line)).or_insert((0, line)).0 -= 1;
+    }
+
+    let changed: Vec<line::Line> = counts.values()
+        .filter(|(count, _)| *count != 0)
+        .map(|(_, line)| **line)
+        .collect();
+
+    let (min, max) = lines_bounds(&changed)?;
+
+    // Bounding box in the pixels of the raster
+    let (offset, scale) = padding_transform(width, height, padding);
+    let size = vec2(width as f32, height as f32);
+    let min = ((min + offset) / scale) * size;
+    let max = ((max + offset) / scale) * size;
+
+    // Past this distance, the pixel values are clamped
+    let cutoff = match spread > 0.0 {
+        true => (0.5 / spread) * 1.001,
+        false => f32::INFINITY
+    };
+    let cutoff = match units {
+        DistanceUnits::Normalized => size * cutoff,
+        DistanceUnits::Pixels => vec2(cutoff, cutoff),
+    };
+
+    // Pixels with their center in the extended box, plus one pixel for rounding
+    let first = |v: f32| (v - 1.5).floor().max(0.0);
+    let x0 = first(min[0] - cutoff[0]).min(width as f32) as u32;
+    let y0 = first(min[1] - cutoff[1]).min(height as f32) as u32;
+    let x1 = (max[0] + cutoff[0] + 1.5).ceil().clamp(0.0, width as f32) as u32;
+    let y1 = (max[1] + cutoff[1] + 1.5).ceil().clamp(0.0, height as f32) as u32;
+
+    match x0 < x1 && y0 < y1 {
+        true => Some(SdfRegion { x: x0, y: y0, width: x1 - x0, height: y1 - y0 }),
+        false => None
     }
+}
+
+/// Rasterize a multi-channel signed distance field (msdf) of the shape defined by `lines`.
+/// 
+/// A msdf stores a different distance in the red, green and blue channel of each pixel. The shape is reconstructed by
+/// taking the median of the three channels (`max(min(r, g), min(max(r, g), b))`), which preserves the sharp corners of the shape
+/// when the sdf is magnified.
+/// 
+/// The arguments are the same as [sdf_generate]. The output raster has 3 channels.
+/// 
+/// # Note
+/// 
+/// * Lines are grouped in contours by following the end point of a line to the start point of the next one. 
+///   Lines of a same contour must be next to each other in `lines`.
+pub fn msdf_generate(
+    width: u32,
+    height: u32,
+    padding: i32,
+    spread: impl Into<Spread>,
+    lines: &[line::Line]
+) -> SdfRaster {
+    generate_multi_channel(width, height, padding, spread.into(), lines, 3)
+}
+
+/// Rasterize a multi-channel and true signed distance field (mtsdf) of the shape defined by `lines`.
+/// 
+/// The red, green and blue channels hold the same values as [msdf_generate], and the alpha channel holds the true distance
+/// computed by [sdf_generate]. The median of the color channels keeps the sharp corners of the shape near the edges, 
+/// while the alpha channel should be used for effects that need the real distance far from the edges (soft shadows, glows, outlines).
+/// 
+/// The arguments are the same as [sdf_generate]. The output raster has 4 channels.
+pub fn mtsdf_generate(
+    width: u32,
+    height: u32,
+    padding: i32,
+    spread: impl Into<Spread>,
+    lines: &[line::Line]
+) -> SdfRaster {
+    generate_multi_channel(width, height, padding, spread.into(), lines, 4)
+}
+
+/// Generates a msdf (`channels == 3`) or a mtsdf (`channels == 4`)
+fn generate_multi_channel(
+    width: u32,
+    height: u32,
+    padding: i32,
+    spread: Spread,
+    lines: &[line::Line],
+    channels: u32,
+) -> SdfRaster {
+    let (spread, units) = spread.resolve(DistanceUnits::Normalized);
+    let padded_lines = distance_space_lines(width, height, units, pad_lines(width, height, padding, lines));
+    let lines: &[line::Line] = &padded_lines;
+    let edges = msdf::color_edges(lines);
+
+    let pixel = pixel_size(width, height, units);
+    let _1w = pixel[0];
+    let _1h = pixel[1];
+
+    let buffer_size = (width * height * channels) as usize;
+    let mut image_buffer: Vec<f32> = vec![0.0; buffer_size];
 
-    // Flip if a pixel is inside or outside the shape
     for y in 0..height {
         let py = (y as f32 + 0.5) * _1h;
-        let scanline = scanline(py, lines);
+        let scanline = scanline(py, lines, FillRule::EvenOdd);
 
         for x in 0..width {
-            let index = (x + (width * y)) as usize;
             let px = (x as f32 + 0.5) * _1w;
-            
-            if scanline_scan(&scanline, px) {
-                image_buffer[index] = 1.0 - image_buffer[index];
+            let index = ((x + (width * y)) * channels) as usize;
+            let inside = scanline_scan(&scanline, px);
+
+            let distances = msdf::pixel_distances(&edges, vec2(px, py));
+            let mut pixel = distances.map(|d| (0.5 + (d * spread)).clamp(0.0, 1.0));
+
+            // The median must agree with the scanline, otherwise the whole pixel is flipped
+            let median = msdf::median(pixel);
+            if (inside && median < 0.5) || (!inside && median > 0.5) {
+                pixel = pixel.map(|v| 1.0 - v);
+            }
+
+            image_buffer[index..index+3].copy_from_slice(&pixel);
+
+            if channels == 4 {
+                // Same computation as `sdf_generate`
+                let distance = match inside {
+                    true => nearest_distance(lines, px, py),
+                    false => -nearest_distance(lines, px, py)
+                };
+                image_buffer[index+3] = sdf_value(distance, spread);
             }
         }
     }
 
+    // Threshold is slightly higher than the value difference between two pixels
+    msdf::correct_errors(&mut image_buffer, width, height, channels as usize, 1.001 * spread * _1w, 1.001 * spread * _1h);
+
     SdfRaster {
-        width: width,
-        height: height,
+        width,
+        height,
+        channels,
         buffer: image_buffer,
+        pixel_range: pixel_range(width, height, spread, units),
     }
 }
 
+/// Compute the signed distance of every pixel of a raster to the shape defined by `lines`, without scaling nor clamping them.
+/// The distances can be used for hit testing, physics, or converted to a sdf later with [signed_distance_to_sdf].
+/// 
+/// # Arguments
+/// 
+/// * `width`, `height`, `padding`, `lines`: Same as [sdf_generate]
+/// * `settings`: The generation settings. `settings.units` set the units of the distances: 
+///   [DistanceUnits::Normalized] for the normalized space of the raster, or [DistanceUnits::Pixels] for pixels of the raster.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+/// 
+/// /// Generate the distances once, then convert them to sdf with different spreads
+/// fn generate_spreads(lines: &[sdf::Line]) -> (sdf::SdfRaster, sdf::SdfRaster) {
+///     let settings = sdf::SdfSettings { units: sdf::DistanceUnits::Pixels, ..Default::default() };
+///     let distances = sdf::signed_distance_generate(64, 64, 2, lines, settings);
+///     (sdf::signed_distance_to_sdf(&distances, 0.25), sdf::signed_distance_to_sdf(&distances, 0.05))
+/// }
+/// ```
+pub fn signed_distance_generate(
+    width: u32,
+    height: u32,
+    padding: i32,
+    lines: &[line::Line],
+    settings: SdfSettings,
+) -> SignedDistanceRaster {
+    let padded_lines = distance_space_lines(width, height, settings.units, pad_lines(width, height, padding, lines));
+    let lines: &[line::Line] = &padded_lines;
+
+    let buffer_size = (width * height) as usize;
+    let mut buffer: Vec<f32> = vec![0.0; buffer_size];
+
+    let pixel = pixel_size(width, height, settings.units);
+    for_each_row(&mut buffer, width as usize, settings.threads, |y, row| {
+        signed_distance_row(pixel, lines, settings, 0, y, row, |distance| distance);
+    });
+
+    SignedDistanceRaster {
+        width,
+        height,
+        units: settings.units,
+        buffer,
+    }
+}
+
+/// Convert a [SignedDistanceRaster] into a [SdfRaster]. The output is the same as [sdf_generate_with_settings] with the same arguments.
+/// 
+/// # Arguments
+/// 
+/// * `distances`: The distances to convert
+/// * `spread`: Control how the gradient in the sdf spread. The distances are scaled by `spread`, in the units of the distances.
+pub fn signed_distance_to_sdf(distances: &SignedDistanceRaster, spread: f32) -> SdfRaster {
+    let SignedDistanceRaster { width, height, units, .. } = *distances;
+    SdfRaster {
+        width,
+        height,
+        channels: 1,
+        buffer: distances.buffer.iter().map(|&distance| sdf_value(distance, spread)).collect(),
+        pixel_range: pixel_range(width, height, spread, units),
+    }
+}
+
+/// Compute the signed distance between `p` and the shape defined by `lines`, without generating a raster. Positive values are inside the shape.
+/// `p` and the distance are in the space of the lines.
+/// 
+/// The distance is the same as the one computed by [sdf_generate] for a pixel centered on `p` (without padding and with [DistanceUnits::Normalized]),
+/// using the [FillRule::EvenOdd] fill rule. See [signed_distance_with_fill_rule] to use another fill rule.
+/// 
+/// # Example
+/// 
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+/// 
+/// let button = sdf::shapes::rectangle(sdf::vec2(0.1, 0.1), sdf::vec2(0.9, 0.5));
+/// assert!((sdf::signed_distance(sdf::vec2(0.5, 0.2), &button) - 0.1).abs() < 1e-6);
+/// assert!((sdf::signed_distance(sdf::vec2(0.5, 0.7), &button) + 0.2).abs() < 1e-6);
+/// ```
+pub fn signed_distance(p: Vec2, lines: &[line::Line]) -> f32 {
+    signed_distance_with_fill_rule(p, lines, FillRule::EvenOdd)
+}
+
+/// Same as [signed_distance], with the fill rule used to decide if `p` is inside the shape
+pub fn signed_distance_with_fill_rule(p: Vec2, lines: &[line::Line], fill_rule: FillRule) -> f32 {
+    let lines = clean_lines(lines);
+    let distance = nearest_distance(&lines, p[0], p[1]);
+    match scanline_scan(&scanline(p[1], &lines, fill_rule), p[0]) {
+        true => distance,
+        false => -distance,
+    }
+}
+
+/// Check if `p` is inside the shape defined by `lines`, using the [FillRule::EvenOdd] fill rule.
+/// The result is the same as the sign of the pixel centered on `p` in [sdf_generate]. See [signed_distance]
+pub fn contains(p: Vec2, lines: &[line::Line]) -> bool {
+    contains_with_fill_rule(p, lines, FillRule::EvenOdd)
+}
+
+/// Same as [contains], with the fill rule used to decide if `p` is inside the shape
+pub fn contains_with_fill_rule(p: Vec2, lines: &[line::Line], fill_rule: FillRule) -> bool {
+    let lines = clean_lines(lines);
+    scanline_scan(&scanline(p[1], &lines, fill_rule), p[0])
+}
+
 /// Convert and [SdfRaster] into a [SdfBitmap].
 /// A bitmap is usually what to you to send to store in a gpu texture.
 /// 
@@ -135,16 +764,13 @@ pub fn sdf_generate(
 pub fn sdf_to_bitmap(sdf: &SdfRaster) -> SdfBitmap {
     let width = sdf.width;
     let height = sdf.height;
-    let mut buffer: Vec<u8> = vec![0u8; (width * height) as usize];
-
-    for x in 0..width {
-        for y in 0..height {
-            let index = (x + (width * y)) as usize;
-            buffer[index] = (sdf.buffer[index] * 255.0) as u8;
-        }
-    }
+    let channels = sdf.channels;
+    let pixel_range = sdf.pixel_range;
+    let buffer: Vec<u8> = sdf.buffer.iter()
+        .map(|&value| (value * 255.0) as u8)
+        .collect();
 
-    SdfBitmap { width, height, buffer }
+    SdfBitmap { width, height, channels, buffer, pixel_range }
 }
 
 /// Saves a sdf output to a file. 
@@ -159,28 +785,24 @@ pub fn sdf_to_bitmap(sdf: &SdfRaster) -> SdfBitmap {
 /// 
 /// # Note
 ///   * Png (or other lossless format) are strongly recommended.
-///   * The file format will use a single byte grayscale pixel format.
+///   * The file format will use a single byte grayscale pixel format for sdf, a rgb pixel format for msdf and a rgba pixel format for mtsdf.
 /// 
 /// # Feature
 /// 
 /// * Requires the `export` feature with the used file format subfeature (ex: png, jpeg, etc)
 #[cfg(feature="export")]
 pub fn sdf_to_file(output_name: &str, sdf: &SdfRaster) -> image::ImageResult<()> {
-    use image::{GrayImage, Luma};
+    use image::{GrayImage, RgbImage, RgbaImage};
 
-    let width = sdf.width;
-    let height = sdf.height;
+    let bitmap = sdf_to_bitmap(sdf);
+    let width = bitmap.width;
+    let height = bitmap.height;
 
-    let mut img = GrayImage::new(width, height);
-    for x in 0..width {
-        for y in 0..height {
-            let index = (x + (width * y)) as usize;
-            let pixel_value = (sdf.buffer[index] * 255.0) as u8;
-            img.put_pixel(x, y, Luma([pixel_value]));
-        }
+    match bitmap.channels {
+        3 => RgbImage::from_raw(width, height, bitmap.buffer).unwrap().save(output_name),
+        4 => RgbaImage::from_raw(width, height, bitmap.buffer).unwrap().save(output_name),
+        _ => GrayImage::from_raw(width, height, bitmap.buffer).unwrap().save(output_name)
     }
-
-    img.save(output_name)
 }
  
 
@@ -267,7 +889,21 @@ pub fn sdf_render_to_file(
 /// The interpolated distance between the 4 nearest pixels
 /// 
 /// Returns the sampled distance. Guaranteed to be between 0.0 and 1.0
+/// 
+/// For a msdf or a mtsdf, each channel is interpolated and the median of the red, green and blue channels is returned
 pub fn sdf_sample(sdf: &SdfRaster, x: f32, y: f32) -> f32 {
+    match sdf.channels {
+        1 => sdf_sample_channel(sdf, 0, x, y),
+        _ => msdf::median([
+            sdf_sample_channel(sdf, 0, x, y),
+            sdf_sample_channel(sdf, 1, x, y),
+            sdf_sample_channel(sdf, 2, x, y),
+        ])
+    }
+}
+
+/// Bilinear sampling of a single channel of `sdf`
+fn sdf_sample_channel(sdf: &SdfRaster, channel: usize, x: f32, y: f32) -> f32 {
     let gx = (x * (sdf.width as f32) - 0.5).max(0.0);
     let gy = (y * (sdf.height as f32) - 0.5).max(0.0);
     let left = gx.floor() as usize;
@@ -279,8 +915,9 @@ pub fn sdf_sample(sdf: &SdfRaster, x: f32, y: f32) -> f32 {
     let bottom = (top+1).min((sdf.height - 1) as usize);
 
     let row_size = sdf.width as usize;   
+    let channels = sdf.channels as usize;
     let get_pixel = |x, y| {
-        sdf.buffer[(row_size*y)+x]
+        sdf.buffer[((row_size*y)+x)*channels + channel]
     };
 
     let p00 = get_pixel(left, top);
@@ -291,43 +928,225 @@ pub fn sdf_sample(sdf: &SdfRaster, x: f32, y: f32) -> f32 {
     mix(mix(p00, p10, wx), mix(p01, p11, wx), wy)
 }
 
-/// Collection of intersection between an horizontal line and multiple other lines.
-#[derive(Debug)]
-struct Scanline {
-    intersections: Vec<f32>,
-}
+/// Write the sdf values of the pixels in `region` of a `width`x`height` raster into `target`. `lines` must be in the distance space.
+fn sdf_region_into<T: SdfValue>(
+    width: u32,
+    height: u32,
+    spread: f32,
+    lines: &[line::Line],
+    settings: SdfSettings,
+    region: SdfRegion,
+    target: SdfTarget<'_, T>,
+) {
+    if region.x + region.width > width || region.y + region.height > height {
+        panic!("Region {:?} is not inside a raster of {:?}x{:?}", region, width, height);
+    }
 
-/// Scan all the intersection for an horizontal line at `y`
-fn scanline(y: f32, lines: &[line::Line]) -> Scanline {
-    let mut scanline = Scanline { intersections: Vec::with_capacity(16) };
-    let mut x = [0.0, 0.0, 0.0];
+    if region.width == 0 || region.height == 0 {
+        return;
+    }
 
-    for line in lines {
-        let count = line.intersections(y, &mut x);
-        for i in 0..count {
-            scanline.intersections.push(x[i]);
+    let (x, y, w, h) = (target.x as usize, target.y as usize, region.width as usize, region.height as usize);
+    let start = y * target.stride;
+    let end = start + ((h - 1) * target.stride) + x + w;
+    if x + w > target.stride || end > target.buffer.len() {
+        panic!("Sdf of {:?}x{:?} at {:?} does not fit in a buffer of {:?} values with a stride of {:?}", w, h, (x, y), target.buffer.len(), target.stride);
+    }
+
+    let pixel = pixel_size(width, height, settings.units);
+    for_each_row(&mut target.buffer[start..end], target.stride, settings.threads, |row_y, row| {
+        sdf_row(pixel, spread, lines, settings, region.x, region.y + row_y, &mut row[x..x+w]);
+    });
+}
+
+/// Compute the sdf values of the row `y` of a raster, starting at the column `x0`. `pixel` is the size of a pixel, see [pixel_size]
+fn sdf_row<T: SdfValue>(pixel: Vec2, spread: f32, lines: &[line::Line], settings: SdfSettings, x0: u32, y: u32, row: &mut [T]) {
+    signed_distance_row(pixel, lines, settings, x0, y, row, |distance| T::from_sdf(sdf_value(distance, spread)));
+}
+
+/// Convert a signed distance to a sdf value
+fn sdf_value(distance: f32, spread: f32) -> f32 {
+    let value = ((1.0 - (distance.abs() * spread)) - 0.5).clamp(0.0, 1.0);
+
+    // Inside distances are positive, including `+0.0`
+    match distance.is_sign_negative() {
+        true => value,
+        false => 1.0 - value
+    }
+}
+
+/// Compute the signed distances of the row `y` of a raster, starting at the column `x0`, and write them in `row` with `convert`.
+/// `pixel` is the size of a pixel, see [pixel_size]
+fn signed_distance_row<T, F: Fn(f32) -> T>(pixel: Vec2, lines: &[line::Line], settings: SdfSettings, x0: u32, y: u32, row: &mut [T], convert: F) {
+    let _1w = pixel[0];
+    let _1h = pixel[1];
+    let py = (y as f32 + 0.5) * _1h;
+    let scanline = scanline(py, lines, settings.fill_rule);
+
+    for (x, value) in row.iter_mut().enumerate() {
+        let px = ((x0 as usize + x) as f32 + 0.5) * _1w;
+
+        // Compute the distance between lines, and flip it if the pixel is inside the shape
+        let mut distance = -nearest_distance(lines, px, py);
+        if scanline_scan(&scanline, px) {
+            distance = -distance;
+        }
+
+        *value = convert(distance);
+    }
+}
+
+/// Return the distance between the point [x, y] and the nearest line, or `f32::MAX` if there are no lines
+fn nearest_distance(lines: &[line::Line], x: f32, y: f32) -> f32 {
+    let mut min_distance = f32::MAX;
+    for line in lines {
+        let d = line.distance(x, y);
+        if d < min_distance {
+            min_distance = d;
         }
     }
 
-    if scanline.intersections.len() > 0 {
-        scanline.intersections.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        scanline.intersections.dedup();
+    min_distance
+}
+
+/// Call `row_fn` for every row of `buffer`. The rows are split in contiguous blocks between `threads` threads.
+/// The last row can be shorter than `row_size`.
+fn for_each_row<T, F>(buffer: &mut [T], row_size: usize, threads: usize, row_fn: F)
+where
+    T: Send,
+    F: Fn(u32, &mut [T]) + Sync,
+{
+    for_each_row_with(buffer, row_size, threads, || (), |_, y, row| row_fn(y, row));
+}
+
+/// Same as [for_each_row], but each thread creates a state with `init` and passes it to `row_fn` for all of its rows
+fn for_each_row_with<T, S, I, F>(buffer: &mut [T], row_size: usize, threads: usize, init: I, row_fn: F)
+where
+    T: Send,
+    I: Fn() -> S + Sync,
+    F: Fn(&mut S, u32, &mut [T]) + Sync,
+{
+    if row_size == 0 {
+        return;
+    }
+
+    let rows = buffer.len().div_ceil(row_size);
+    let threads = thread_count(threads).min(rows);
+    if threads <= 1 {
+        let mut state = init();
+        for (y, row) in buffer.chunks_mut(row_size).enumerate() {
+            row_fn(&mut state, y as u32, row);
+        }
+        return;
     }
-    
+
+    let rows_per_thread = rows.div_ceil(threads);
+    let (init, row_fn) = (&init, &row_fn);
+    std::thread::scope(|scope| {
+        for (i, block) in buffer.chunks_mut(rows_per_thread * row_size).enumerate() {
+            scope.spawn(move || {
+                let mut state = init();
+                for (j, row) in block.chunks_mut(row_size).enumerate() {
+                    row_fn(&mut state, (i * rows_per_thread + j) as u32, row);
+                }
+            });
+        }
+    });
+}
+
+/// Return the number of threads to use for a `threads` setting value
+pub(crate) fn thread_count(threads: usize) -> usize {
+    match threads {
+        0 => std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+        n => n
+    }
+}
+
+/// Map `lines` in the work area of a raster of `width`x`height` pixels with `padding` pixels on each side
+fn pad_lines(width: u32, height: u32, padding: i32, lines: &[line::Line]) -> Cow<'_, [line::Line]> {
+    let lines = clean_lines(lines);
+    if padding == 0 {
+        return lines;
+    }
+
+    let (offset, scale) = padding_transform(width, height, padding);
+    let padded_lines = lines.iter()
+        .map(|line| line.normalize_to_with_offset(-offset[0], -offset[1], scale[0], scale[1]))
+        .collect();
+
+    Cow::Owned(padded_lines)
+}
+
+/// Return the size of a pixel in the space where the distances are computed
+fn pixel_size(width: u32, height: u32, units: DistanceUnits) -> Vec2 {
+    match units {
+        DistanceUnits::Normalized => vec2(1.0 / width as f32, 1.0 / height as f32),
+        DistanceUnits::Pixels => vec2(1.0, 1.0),
+    }
+}
+
+/// Return the distance range (in pixels) covered by the values of a sdf
+fn pixel_range(width: u32, height: u32, spread: f32, units: DistanceUnits) -> Vec2 {
+    let range = vec2(1.0 / spread, 1.0 / spread);
+    match units {
+        DistanceUnits::Normalized => range * vec2(width as f32, height as f32),
+        DistanceUnits::Pixels => range,
+    }
+}
+
+/// Move the normalized `lines` to the space where the distances are computed
+fn distance_space_lines(width: u32, height: u32, units: DistanceUnits, lines: Cow<'_, [line::Line]>) -> Cow<'_, [line::Line]> {
+    match units {
+        DistanceUnits::Normalized => lines,
+        DistanceUnits::Pixels => {
+            let pixel = pixel_size(width, height, DistanceUnits::Normalized);
+            Cow::Owned(lines.iter().map(|line| line.normalize_to(pixel[0], pixel[1])).collect())
+        }
+    }
+}
+
+/// Return the `offset` and `scale` mapping the coordinates of a padded raster to the coordinates of the shape. 
+/// `shape = (padded * scale) - offset`
+fn padding_transform(width: u32, height: u32, padding: i32) -> (Vec2, Vec2) {
+    let padding_width_normalized = padding as f32 / width as f32;
+    let padding_height_normalized = padding as f32 / height as f32;
+    let offset = vec2(padding_width_normalized, padding_height_normalized);
+    let scale = vec2(1.0 + (padding_width_normalized * 2.0), 1.0 + (padding_height_normalized * 2.0));
+    (offset, scale)
+}
+
+/// Collection of intersection between an horizontal line and multiple other lines.
+#[derive(Debug)]
+struct Scanline {
+    /// Position and direction of the intersections
+    intersections: Vec<(f32, i32)>,
+    fill_rule: FillRule,
+}
+
+/// Scan all the intersection for an horizontal line at `y`
+fn scanline(y: f32, lines: &[line::Line], fill_rule: FillRule) -> Scanline {
+    let mut scanline = Scanline { intersections: Vec::with_capacity(16), fill_rule };
+    let mut x = [0.0, 0.0, 0.0];
+    let mut directions = [0, 0, 0];
+
+    for line in lines {
+        let count = line.intersections_with_direction(y, &mut x, &mut directions);
+        for i in 0..count {
+            scanline.intersections.push((x[i], directions[i]));
+        }
+    }
+
+    // The crossings are counted exactly once by `intersections_with_direction`, even at shared vertices, so they must not be deduplicated
     scanline
 }
 
 /// Check if a point `x` in inside or outside `scanline`
 fn scanline_scan(scanline: &Scanline, x: f32) -> bool {
-    let count = scanline
-        .intersections
-        .iter()
-        .fold(0u32, |acc, &inter| match x < inter {
-            true => acc+1,
-            false => acc
-        });
-
-    count % 2 == 1
+    let crossings = scanline.intersections.iter().filter(|&&(inter, _)| x < inter);
+    match scanline.fill_rule {
+        FillRule::EvenOdd => crossings.count() % 2 == 1,
+        FillRule::NonZero => crossings.map(|&(_, direction)| direction).sum::<i32>() != 0,
+    }
 }
 
 /// Linear interpolation function
@@ -378,6 +1197,546 @@ mod tests {
         assert_eq!(line.distance(1.0, 1.0), 0.0);
     }
 
+    #[test]
+    fn test_shape() {
+        // A square with a triangle hole. The square is closed by `move_to`, the triangle by `close`
+        let mut shape = Shape::new();
+        shape.move_to(0.1, 0.1)
+            .line_to(0.9, 0.1)
+            .quad_to(1.0, 0.5, 0.9, 0.9)
+            .line_to(0.1, 0.9)
+            .move_to(0.5, 0.2)
+            .line_to(0.7, 0.7)
+            .cubic_to(0.6, 0.8, 0.4, 0.8, 0.3, 0.7)
+            .close();
+
+        let contours: Vec<Contour> = shape.contours().collect();
+        assert_eq!(contours.len(), 2);
+        assert_eq!(contours[0].len(), 4);
+        assert_eq!(contours[1].len(), 3);
+        assert!(contours.iter().all(|contour| contour.is_closed()));
+        assert_eq!(contours[0][3], Line::Line { start: vec2(0.1, 0.9), end: vec2(0.1, 0.1) });
+        assert_eq!(contours[1][2], Line::Line { start: vec2(0.3, 0.7), end: vec2(0.5, 0.2) });
+        assert_eq!(shape.len(), 7);
+
+        // Closing twice or an empty contour does nothing
+        shape.close().move_to(0.0, 0.0).close();
+        assert_eq!(shape.contours().count(), 2);
+
+        // Conversion from and to lines
+        let lines = shape.clone().into_lines();
+        assert_eq!(Shape::from_lines(&lines), shape);
+        assert!(sdf_generate(32, 32, 2, 6.0, &shape).buffer == sdf_generate(32, 32, 2, 6.0, &lines).buffer);
+
+        let open = Shape::from(&lines[..2]);
+        assert_eq!(open.len(), 3);
+        assert_eq!(open[2], Line::Line { start: vec2(0.9, 0.9), end: vec2(0.1, 0.1) });
+
+        // A contour without `close` stays open
+        let mut shape = Shape::new();
+        shape.move_to(0.1, 0.1).line_to(0.9, 0.1).line_to(0.5, 0.9);
+        assert!(!shape.contours().next().unwrap().is_closed());
+        assert_eq!(validate_lines(&shape), vec![ShapeIssue::OpenContour { first: 0, last: 1 }]);
+    }
+
+    #[test]
+    fn test_orientation() {
+        let square = |min: f32, max: f32| {
+            let mut shape = Shape::new();
+            shape.move_to(min, min).line_to(max, min).line_to(max, max).line_to(min, max).close();
+            shape.into_lines()
+        };
+
+        // Signed area of each kind of line
+        assert!((signed_area(&square(0.25, 0.75)) - 0.25).abs() < 1e-6);
+        let k = 0.552_284_8 * 0.25;
+        let mut circle = Shape::new();
+        circle.move_to(0.75, 0.5)
+            .cubic_to(0.75, 0.5 + k, 0.5 + k, 0.75, 0.5, 0.75)
+            .cubic_to(0.5 - k, 0.75, 0.25, 0.5 + k, 0.25, 0.5)
+            .cubic_to(0.25, 0.5 - k, 0.5 - k, 0.25, 0.5, 0.25)
+            .cubic_to(0.5 + k, 0.25, 0.75, 0.5 - k, 0.75, 0.5);
+        assert!((signed_area(&circle) - (std::f32::consts::PI * 0.25 * 0.25)).abs() < 1e-4);
+
+        let quad = Line::Quad { start: vec2(0.2, 0.3), end: vec2(0.8, 0.3), control: vec2(0.5, 0.9) };
+        let mut lens = [quad, Line::Line { start: vec2(0.8, 0.3), end: vec2(0.2, 0.3) }];
+        assert!((signed_area(&lens) + (2.0 / 3.0) * 0.6 * 0.3).abs() < 1e-6);
+
+        // Reversing
+        reverse_lines(&mut lens);
+        assert!((signed_area(&lens) - (2.0 / 3.0) * 0.6 * 0.3).abs() < 1e-6);
+        assert_eq!(lens[1], Line::Quad { start: vec2(0.8, 0.3), end: vec2(0.2, 0.3), control: vec2(0.5, 0.9) });
+        let mut curve = Line::Curve { start: vec2(0.1, 0.1), end: vec2(0.9, 0.9), first_control: vec2(0.5, 0.0), second_control: vec2(1.0, 0.5) };
+        curve.reverse();
+        assert_eq!(curve, Line::Curve { start: vec2(0.9, 0.9), end: vec2(0.1, 0.1), first_control: vec2(1.0, 0.5), second_control: vec2(0.5, 0.0) });
+
+        // A square with a hole and an island in the hole, all in the same direction
+        let mut lines = square(0.1, 0.9);
+        lines.extend(square(0.3, 0.7));
+        lines.extend(square(0.4, 0.6));
+        let mut shape = Shape::from_lines(&lines);
+        assert_eq!(shape.contours().map(|c| c.winding()).collect::<Vec<_>>(), vec![1, 1, 1]);
+
+        let non_zero = SdfSettings { fill_rule: FillRule::NonZero, ..Default::default() };
+        let expected = sdf_generate(32, 32, 2, 6.0, &lines);
+        assert!(sdf_generate_with_settings(32, 32, 2, 6.0, &shape, non_zero).buffer != expected.buffer);
+
+        shape.orient_contours();
+        assert_eq!(shape.contours().map(|c| c.winding()).collect::<Vec<_>>(), vec![1, -1, 1]);
+        assert!(sdf_generate_with_settings(32, 32, 2, 6.0, &shape, non_zero).buffer == expected.buffer);
+
+        // Already oriented
+        let oriented = shape.clone();
+        reverse_lines(&mut lines[..4]);
+        let mut shape = Shape::from_lines(&lines);
+        shape.orient_contours();
+        assert_eq!(shape.contours().map(|c| c.winding()).collect::<Vec<_>>(), vec![1, -1, 1]);
+        shape.orient_contours();
+        assert_eq!(shape.contours().nth(1), oriented.contours().nth(1));
+    }
+
+    #[test]
+    fn test_degenerate_lines() {
+        let triangle = [
+            Line::Line { start: vec2(0.5, 0.1), end: vec2(0.9, 0.9) },
+            Line::Line { start: vec2(0.9, 0.9), end: vec2(0.1, 0.9) },
+            Line::Line { start: vec2(0.1, 0.9), end: vec2(0.5, 0.1) },
+        ];
+
+        // Same triangle with a straight quad, a zero length line and a line with NaN coordinates
+        let degenerate = [
+            Line::Line { start: vec2(0.5, 0.1), end: vec2(0.9, 0.9) },
+            Line::Line { start: vec2(0.9, 0.9), end: vec2(0.9, 0.9) },
+            Line::Quad { start: vec2(0.9, 0.9), end: vec2(0.1, 0.9), control: vec2(0.5, 0.9) },
+            Line::Curve { start: vec2(0.1, 0.9), end: vec2(0.5, 0.1), first_control: vec2(0.2, 0.7), second_control: vec2(0.4, 0.3) },
+            Line::Line { start: vec2(f32::NAN, 0.0), end: vec2(0.5, 0.5) },
+        ];
+
+        let expected = sdf_generate(32, 32, 2, 6.0, &triangle);
+        let sdf = sdf_generate(32, 32, 2, 6.0, &degenerate);
+        assert!(sdf.buffer == expected.buffer);
+        assert!(PreparedShape::new(&degenerate).sdf_generate(32, 32, 2, 6.0).buffer == expected.buffer);
+
+        let msdf = msdf_generate(32, 32, 2, 6.0, &degenerate);
+        assert!(msdf.buffer.iter().all(|v| !v.is_nan()));
+
+        // Distances to degenerate lines
+        let point = Line::Line { start: vec2(0.5, 0.5), end: vec2(0.5, 0.5) };
+        assert_eq!(point.distance(0.5, 0.8), 0.3);
+        let quad = Line::Quad { start: vec2(0.1, 0.5), end: vec2(0.9, 0.5), control: vec2(0.5, 0.5) };
+        assert!((quad.distance(0.5, 0.8) - 0.3).abs() < 1e-6);
+        assert!((quad.distance(1.0, 0.5) - 0.1).abs() < 1e-6);
+        let quad = Line::Quad { start: vec2(0.5, 0.5), end: vec2(0.5, 0.5), control: vec2(0.5, 0.5) };
+        assert_eq!(quad.distance(0.5, 0.8), 0.3);
+
+        // Straight quads are measured as lines, and the parameter follows the curve even if the control point is not in the middle
+        let segment = Line::Line { start: vec2(0.1, 0.5), end: vec2(0.9, 0.5) };
+        for control in [vec2(0.5, 0.5), vec2(0.2, 0.5), vec2(0.9, 0.5), vec2(0.1, 0.5)] {
+            let quad = Line::Quad { start: vec2(0.1, 0.5), end: vec2(0.9, 0.5), control };
+            for p in [vec2(0.6, 0.8), vec2(0.15, 0.2), vec2(0.0, 0.5), vec2(1.0, 0.6)] {
+                assert_eq!(quad.distance(p[0], p[1]), segment.distance(p[0], p[1]));
+                let closest = quad.closest_point(p);
+                assert!((closest.point - segment.closest_point(p).point).length() < 1e-5, "{:?} {:?}", control, p);
+            }
+        }
+
+        // Validation
+        assert_eq!(validate_lines(&triangle), Vec::new());
+        assert_eq!(validate_lines(&degenerate), vec![
+            ShapeIssue::NonFinite { index: 4 },
+            ShapeIssue::OpenContour { first: 4, last: 4 },
+        ]);
+
+        let open = [
+            Line::Line { start: vec2(0.5, 0.1), end: vec2(1.5, 0.9) },
+            Line::Line { start: vec2(1.5, 0.9), end: vec2(0.1, 0.9) },
+            Line::Line { start: vec2(0.5, 0.2), end: vec2(0.6, 0.3) },
+            Line::Line { start: vec2(0.6, 0.3), end: vec2(0.5, 0.2) },
+        ];
+
+        assert_eq!(validate_lines(&open), vec![
+            ShapeIssue::OutOfRange { index: 0 },
+            ShapeIssue::OutOfRange { index: 1 },
+            ShapeIssue::OpenContour { first: 0, last: 1 },
+        ]);
+    }
+
+    #[test]
+    fn test_cubic_distance() {
+        // Compare the cubic distance with a dense sampling of the curve, on curves with a loop, a cusp, an inflection and collinear points
+        let curves = [
+            Line::Curve { start: vec2(0.1, 0.9), end: vec2(0.9, 0.9), first_control: vec2(1.2, 0.0), second_control: vec2(-0.2, 0.0) },
+            Line::Curve { start: vec2(0.1, 0.9), end: vec2(0.9, 0.9), first_control: vec2(0.9, 0.1), second_control: vec2(0.1, 0.1) },
+            Line::Curve { start: vec2(0.1, 0.1), end: vec2(0.9, 0.9), first_control: vec2(0.9, 0.1), second_control: vec2(0.1, 0.9) },
+            Line::Curve { start: vec2(0.1, 0.1), end: vec2(0.5, 0.5), first_control: vec2(0.9, 0.9), second_control: vec2(0.3, 0.3) },
+            Line::Curve { start: vec2(0.0, 0.0), end: vec2(1.0, 1.0), first_control: vec2(0.8, 0.0), second_control: vec2(1.0, 0.2) },
+            Line::Curve { start: vec2(0.3, 0.3), end: vec2(0.3, 0.3), first_control: vec2(0.3, 0.3), second_control: vec2(0.3, 0.3) },
+        ];
+
+        let reference = |curve: &Line, x: f32, y: f32| {
+            let Line::Curve { start, end, first_control, second_control } = *curve else { unreachable!() };
+            let point = |t: f64| {
+                let mt = 1.0 - t;
+                let v = |i: usize| (start[i] as f64)*mt*mt*mt + 3.0*(first_control[i] as f64)*mt*mt*t + 3.0*(second_control[i] as f64)*mt*t*t + (end[i] as f64)*t*t*t;
+                ((v(0) - x as f64).powi(2) + (v(1) - y as f64).powi(2)).sqrt()
+            };
+
+            // Coarse sampling, then a fine sampling around every local minimum
+            const STEPS: usize = 1000;
+            let distances: Vec<f64> = (0..=STEPS).map(|i| point(i as f64 / STEPS as f64)).collect();
+            let mut min = f64::MAX;
+            for i in 0..=STEPS {
+                let local = (i == 0 || distances[i] < distances[i-1]) && (i == STEPS || distances[i] <= distances[i+1]);
+                if !local {
+                    continue;
+                }
+
+                for j in 0..=400 {
+                    let t = ((i as f64 - 1.0 + (j as f64 / 200.0)) / STEPS as f64).clamp(0.0, 1.0);
+                    min = min.min(point(t));
+                }
+            }
+
+            min as f32
+        };
+
+        for curve in curves.iter() {
+            for y in 0..=24 {
+                for x in 0..=24 {
+                    let (x, y) = ((x as f32 / 24.0) * 1.2 - 0.1, (y as f32 / 24.0) * 1.2 - 0.1);
+                    let expected = reference(curve, x, y);
+                    let distance = curve.distance(x, y);
+                    assert!((distance - expected).abs() < 1e-5, "{:?} at {:?}: {:?} != {:?}", curve, (x, y), distance, expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        // Compare the bounds with a dense sampling of the lines
+        let lines = [
+            Line::Line { start: vec2(0.9, 0.1), end: vec2(0.2, 0.7) },
+            Line::Quad { start: vec2(0.0, 0.0), end: vec2(1.0, 0.0), control: vec2(0.5, 1.0) },
+            Line::Quad { start: vec2(0.1, 0.2), end: vec2(0.3, 0.9), control: vec2(-0.4, 0.5) },
+            Line::Curve { start: vec2(0.1, 0.9), end: vec2(0.9, 0.9), first_control: vec2(1.2, 0.0), second_control: vec2(-0.2, 0.0) },
+            Line::Curve { start: vec2(0.1, 0.1), end: vec2(0.9, 0.9), first_control: vec2(0.9, 0.1), second_control: vec2(0.1, 0.9) },
+            Line::Curve { start: vec2(0.1, 0.1), end: vec2(0.5, 0.5), first_control: vec2(0.9, 0.9), second_control: vec2(0.3, 0.3) },
+            Line::Arc { center: vec2(0.5, 0.5), radius: vec2(0.4, 0.2), rotation: 0.3, start_angle: 2.0, sweep_angle: -4.0 },
+        ];
+
+        for line in lines.iter() {
+            let (min, max) = line.bounds();
+            let (mut expected_min, mut expected_max) = (vec2(f32::MAX, f32::MAX), vec2(f32::MIN, f32::MIN));
+            for i in 0..=10000 {
+                let p = line.point(i as f32 / 10000.0);
+                expected_min = vec2(expected_min[0].min(p[0]), expected_min[1].min(p[1]));
+                expected_max = vec2(expected_max[0].max(p[0]), expected_max[1].max(p[1]));
+            }
+
+            assert!((min - expected_min).length() < 1e-5 && (max - expected_max).length() < 1e-5, "{:?}: {:?}", line, (min, max));
+        }
+
+        assert_eq!(lines[1].bounds(), (vec2(0.0, 0.0), vec2(1.0, 0.5)));
+        assert_eq!(lines_bounds(&lines[..2]), Some((vec2(0.0, 0.0), vec2(1.0, 0.7))));
+        assert_eq!(lines_bounds(&[]), None);
+
+        // The bulge of a curve is included in the bounds of a glyph
+        #[cfg(feature="font")]
+        {
+            use ttf_parser::OutlineBuilder;
+
+            let mut geometry = font_geometry::FontGeometry::new();
+            geometry.move_to(0.0, 0.0);
+            geometry.quad_to(50.0, 100.0, 100.0, 0.0);
+            geometry.close();
+            geometry.finalize();
+
+            assert_eq!((geometry.bounds.ymin, geometry.bounds.height), (0.0, 50.0));
+            assert_eq!(lines_bounds(&geometry.lines), Some((vec2(0.0, 0.0), vec2(1.0, 1.0))));
+        }
+    }
+
+    #[test]
+    fn test_flatten() {
+        let lines = [
+            Line::Line { start: vec2(0.9, 0.1), end: vec2(0.2, 0.7) },
+            Line::Quad { start: vec2(0.0, 0.0), end: vec2(1.0, 0.0), control: vec2(0.5, 1.0) },
+            Line::Curve { start: vec2(0.1, 0.9), end: vec2(0.9, 0.9), first_control: vec2(1.2, 0.0), second_control: vec2(-0.2, 0.0) },
+            Line::Curve { start: vec2(0.1, 0.1), end: vec2(0.9, 0.9), first_control: vec2(0.9, 0.1), second_control: vec2(0.1, 0.9) },
+            Line::Curve { start: vec2(0.1, 0.1), end: vec2(0.5, 0.5), first_control: vec2(0.9, 0.9), second_control: vec2(0.3, 0.3) },
+            Line::Arc { center: vec2(0.5, 0.5), radius: vec2(0.4, 0.2), rotation: 0.3, start_angle: 2.0, sweep_angle: -4.0 },
+        ];
+
+        let samples = |line: Line| (0..=1000).map(move |i| line.point(i as f32 / 1000.0));
+        for line in lines.iter() {
+            // Split
+            for t in [0.0, 0.25, 0.5, 0.9] {
+                let (first, second) = line.split(t);
+                assert!((first.end() - line.point(t)).length() < 1e-6 && (second.start() - line.point(t)).length() < 1e-6);
+                for i in 0..=10 {
+                    let u = i as f32 / 10.0;
+                    assert!((first.point(u) - line.point(u * t)).length() < 1e-5, "{:?} {} {}", line, t, u);
+                    assert!((second.point(u) - line.point(t + u * (1.0 - t))).length() < 1e-5, "{:?} {} {}", line, t, u);
+                }
+            }
+
+            for tolerance in [0.01, 0.001] {
+                // Flatten: the points are on the line, and the line is near the polyline
+                let mut points = vec![line.start()];
+                line.flatten(tolerance, &mut points);
+                assert_eq!(*points.last().unwrap(), line.end());
+                assert!(points.iter().all(|p| line.distance(p[0], p[1]) < 1e-5));
+
+                let polyline: Vec<Line> = points.windows(2).map(|p| Line::Line { start: p[0], end: p[1] }).collect();
+                for p in samples(*line) {
+                    let distance = polyline.iter().map(|segment| segment.distance(p[0], p[1])).fold(f32::MAX, f32::min);
+                    assert!(distance <= tolerance * 1.001, "{:?} {}: {}", line, tolerance, distance);
+                }
+
+                // Cubic to quads, within the tolerance in both directions
+                let quads = line.to_quads(tolerance);
+                match line {
+                    Line::Curve { .. } => assert!(quads.iter().all(|quad| matches!(quad, Line::Quad { .. }))),
+                    _ => assert_eq!(quads, vec![*line]),
+                }
+
+                assert!((quads[0].start() - line.start()).length() < 1e-6 && (quads[quads.len() - 1].end() - line.end()).length() < 1e-6);
+                for p in samples(*line) {
+                    let distance = quads.iter().map(|quad| quad.distance(p[0], p[1])).fold(f32::MAX, f32::min);
+                    assert!(distance <= tolerance * 1.001 + 1e-6, "{:?} {}: {}", line, tolerance, distance);
+                }
+
+                for p in quads.iter().flat_map(|quad| samples(*quad)) {
+                    assert!(line.distance(p[0], p[1]) <= tolerance * 1.001 + 1e-6, "{:?} {}", line, tolerance);
+                }
+            }
+        }
+
+        // Lines only add their end point, and a smaller tolerance gives more points, up to 1024 per line
+        let mut points = Vec::new();
+        lines[0].flatten(0.001, &mut points);
+        assert_eq!(points, vec![lines[0].end()]);
+
+        let count = |line: &Line, tolerance: f32| {
+            let mut points = Vec::new();
+            line.flatten(tolerance, &mut points);
+            points.len()
+        };
+        assert!(count(&lines[2], 0.0001) > count(&lines[2], 0.01));
+        assert_eq!(count(&lines[2], 0.0), 1024);
+        assert!(lines[2].to_quads(0.0001).len() > lines[2].to_quads(0.01).len());
+        assert_eq!(lines[2].to_quads(0.0).len(), 1024);
+
+        // Polylines of the contours
+        let mut shape = Shape::new();
+        shape.move_to(0.1, 0.1).line_to(0.4, 0.1).quad_to(0.4, 0.4, 0.1, 0.4).close();
+        shape.move_to(0.6, 0.6).line_to(0.9, 0.6).line_to(0.9, 0.9).close();
+
+        let polylines = flatten_lines(&shape, 0.001);
+        assert_eq!(polylines.len(), 2);
+        assert_eq!(polylines[1], vec![vec2(0.6, 0.6), vec2(0.9, 0.6), vec2(0.9, 0.9), vec2(0.6, 0.6)]);
+        assert!(polylines[0].len() > 4);
+        assert_eq!((polylines[0][0], polylines[0][polylines[0].len() - 1]), (vec2(0.1, 0.1), vec2(0.1, 0.1)));
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let lines = [
+            Line::Line { start: vec2(0.9, 0.1), end: vec2(0.2, 0.7) },
+            Line::Quad { start: vec2(0.0, 0.0), end: vec2(1.0, 0.0), control: vec2(0.5, 1.0) },
+            Line::Curve { start: vec2(0.1, 0.9), end: vec2(0.9, 0.9), first_control: vec2(1.2, 0.0), second_control: vec2(-0.2, 0.0) },
+            Line::Curve { start: vec2(0.1, 0.1), end: vec2(0.9, 0.9), first_control: vec2(0.9, 0.1), second_control: vec2(0.1, 0.9) },
+            Line::Arc { center: vec2(0.5, 0.5), radius: vec2(0.4, 0.2), rotation: 0.3, start_angle: 2.0, sweep_angle: -4.0 },
+        ];
+
+        for line in lines.iter() {
+            let samples: Vec<Vec2> = (0..=2000).map(|i| line.point(i as f32 / 2000.0)).collect();
+            for y in 0..=12 {
+                for x in 0..=12 {
+                    let p = vec2((x as f32 / 12.0) * 1.2 - 0.1, (y as f32 / 12.0) * 1.2 - 0.1);
+                    let closest = line.closest_point(p);
+                    let offset = p - closest.point;
+
+                    assert!((0.0..=1.0).contains(&closest.t));
+                    assert!((closest.point - line.point(closest.t)).length() < 1e-6);
+                    assert!((closest.distance.abs() - line.distance(p[0], p[1])).abs() < 1e-6);
+                    assert!((offset.length() - closest.distance.abs()).abs() < 1e-5, "{:?} at {:?}: {:?}", line, p, closest);
+
+                    let expected = samples.iter().map(|s| (p - *s).length()).fold(f32::MAX, f32::min);
+                    assert!((closest.distance.abs() - expected).abs() < 1e-3, "{:?} at {:?}: {:?}", line, p, closest);
+
+                    // The tangent is a unit vector along the line, and the sign is the side of the line
+                    assert!((closest.tangent.length() - 1.0).abs() < 1e-5);
+                    assert!(closest.tangent.cross(line.direction(closest.t).normalize()).abs() < 1e-5);
+                    assert!(closest.tangent.cross(offset) * closest.distance >= -1e-6);
+                    assert!(closest.pseudo_distance.abs() <= closest.distance.abs() + 1e-6);
+                    assert!(closest.pseudo_distance * closest.distance >= 0.0);
+                }
+            }
+        }
+
+        // Past the end points, the pseudo distance is the distance to the tangents
+        let line = Line::Line { start: vec2(0.0, 0.0), end: vec2(1.0, 0.0) };
+        let closest = line.closest_point(vec2(0.5, 0.5));
+        assert_eq!(closest, ClosestPoint { t: 0.5, point: vec2(0.5, 0.0), tangent: vec2(1.0, 0.0), distance: 0.5, pseudo_distance: 0.5 });
+        let closest = line.closest_point(vec2(-1.0, -0.25));
+        assert_eq!((closest.t, closest.distance, closest.pseudo_distance), (0.0, -(1.0f32 + 0.0625).sqrt(), -0.25));
+        let closest = line.closest_point(vec2(4.0, 3.0));
+        assert_eq!((closest.t, closest.distance, closest.pseudo_distance), (1.0, 18f32.sqrt(), 3.0));
+
+        // Lines without length have no tangent, and their distances are not signed
+        let point = vec2(0.5, 0.5);
+        let degenerate = [
+            Line::Line { start: point, end: point },
+            Line::Quad { start: point, end: point, control: point },
+            Line::Curve { start: point, end: point, first_control: point, second_control: point },
+        ];
+
+        for line in degenerate {
+            let closest = line.closest_point(vec2(0.8, 0.1));
+            assert_eq!(closest, ClosestPoint { t: 0.0, point, tangent: vec2(0.0, 0.0), distance: 0.5, pseudo_distance: 0.5 }, "{:?}", line);
+        }
+
+        // Positive inside a contour with a positive signed area
+        let circle = shapes::circle(vec2(0.5, 0.5), 0.3);
+        for (p, inside) in [(vec2(0.5, 0.5), true), (vec2(0.7, 0.4), true), (vec2(0.1, 0.5), false), (vec2(0.9, 0.9), false)] {
+            let closest = circle.iter()
+                .map(|line| line.closest_point(p))
+                .min_by(|a, b| a.distance.abs().partial_cmp(&b.distance.abs()).unwrap())
+                .unwrap();
+
+            assert_eq!(closest.distance > 0.0, inside, "{:?}", p);
+            assert!((closest.distance.abs() - (0.3 - (p - vec2(0.5, 0.5)).length()).abs()).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_arc() {
+        use std::f32::consts::{PI, TAU};
+        let arcs = [
+            Line::Arc { center: vec2(0.5, 0.5), radius: vec2(0.3, 0.3), rotation: 0.0, start_angle: 0.0, sweep_angle: TAU },
+            Line::Arc { center: vec2(0.5, 0.5), radius: vec2(0.4, 0.15), rotation: 0.6, start_angle: -1.0, sweep_angle: 4.0 },
+            Line::Arc { center: vec2(0.4, 0.6), radius: vec2(0.1, 0.35), rotation: -2.0, start_angle: 2.5, sweep_angle: -5.5 },
+            Line::Arc { center: vec2(0.5, 0.5), radius: vec2(0.35, 0.2), rotation: 0.2, start_angle: 0.3, sweep_angle: 0.4 },
+        ];
+
+        let sample = |arc: &Line, steps: usize| -> Vec<Vec2> {
+            let Line::Arc { center, radius, rotation, start_angle, sweep_angle } = *arc else { unreachable!() };
+            (0..=steps).map(|i| {
+                let angle = (start_angle as f64) + (sweep_angle as f64) * (i as f64 / steps as f64);
+                let (x, y) = (radius[0] as f64 * angle.cos(), radius[1] as f64 * angle.sin());
+                let (sin, cos) = (rotation as f64).sin_cos();
+                vec2((center[0] as f64 + cos*x - sin*y) as f32, (center[1] as f64 + sin*x + cos*y) as f32)
+            }).collect()
+        };
+
+        for arc in arcs.iter() {
+            let points = sample(arc, 20000);
+
+            // Distance
+            for y in 0..=16 {
+                for x in 0..=16 {
+                    let p = vec2((x as f32 / 16.0) * 1.2 - 0.1, (y as f32 / 16.0) * 1.2 - 0.1);
+                    let expected = points.iter().map(|&q| (q - p).length()).fold(f32::MAX, f32::min);
+                    let distance = arc.distance(p[0], p[1]);
+                    assert!((distance - expected).abs() < 1e-5, "{:?} at {:?}: {:?} != {:?}", arc, p, distance, expected);
+                    assert!(distance <= expected + 1e-6);
+                }
+            }
+
+            // Bounds
+            let (min, max) = arc.bounds();
+            let expected_min = points.iter().fold(vec2(f32::MAX, f32::MAX), |m, p| vec2(m[0].min(p[0]), m[1].min(p[1])));
+            let expected_max = points.iter().fold(vec2(f32::MIN, f32::MIN), |m, p| vec2(m[0].max(p[0]), m[1].max(p[1])));
+            assert!((min - expected_min).length() < 1e-5 && (max - expected_max).length() < 1e-5, "{:?}: {:?}", arc, (min, max));
+
+            // Intersections
+            let mut out = [0.0; 3];
+            for i in 0..=20 {
+                let y = (i as f32 + 0.37) / 20.0;
+                let count = arc.intersections(y, &mut out);
+                let expected = points.windows(2).filter(|w| (w[0][1] < y) != (w[1][1] < y)).count();
+                assert_eq!(count, expected, "{:?} at {}", arc, y);
+                for &x in out[..count].iter() {
+                    assert!(arc.distance(x, y) < 1e-5);
+                }
+            }
+
+            // Normalize, flip, reverse and split
+            let mut normalized = *arc;
+            normalized.normalize_with_offset(0.1, 0.2, 2.0, 0.5);
+            let mut flipped = normalized;
+            flipped.flip_y();
+            let mut reversed = *arc;
+            reversed.reverse();
+            let (first, second) = arc.split(0.3);
+            for i in 0..=10 {
+                let t = i as f32 / 10.0;
+                let p = arc.point(t);
+                let n = (p - vec2(0.1, 0.2)) / vec2(2.0, 0.5);
+                assert!((normalized.point(t) - n).length() < 1e-5, "{:?}", normalized);
+                assert!((flipped.point(t) - vec2(n[0], 1.0 - n[1])).length() < 1e-5, "{:?}", flipped);
+                assert!((reversed.point(1.0 - t) - p).length() < 1e-5);
+                assert!((first.point(t) - arc.point(t * 0.3)).length() < 1e-5);
+                assert!((second.point(t) - arc.point(0.3 + t * 0.7)).length() < 1e-5);
+            }
+
+            // Signed area, compared with the polygon of the samples closed by a line
+            let expected: f32 = points.windows(2).map(|w| w[0].cross(w[1]) / 2.0).sum::<f32>() + points[points.len() - 1].cross(points[0]) / 2.0;
+            let chord = Line::Line { start: arc.end(), end: arc.start() };
+            assert!((arc.signed_area() + chord.signed_area() - expected).abs() < 1e-5);
+            assert!((reversed.signed_area() + arc.signed_area()).abs() < 1e-6);
+        }
+
+        assert!((arcs[0].signed_area() - PI * 0.09).abs() < 1e-6);
+
+        // A full turn is a closed contour, with the same sdf as a circle
+        let circle = [arcs[0]];
+        assert_eq!(validate_lines(&circle), Vec::new());
+
+        let (size, spread) = (64, 4.0);
+        let sdf = sdf_generate(size, size, 0, spread, &circle);
+        let prepared = PreparedShape::new(&circle).sdf_generate(size, size, 0, spread);
+        for y in 0..size {
+            for x in 0..size {
+                let p = vec2((x as f32 + 0.5) / size as f32, (y as f32 + 0.5) / size as f32);
+                let expected = (0.5 + (0.3 - (p - vec2(0.5, 0.5)).length()) * spread).clamp(0.0, 1.0);
+                let value = sdf.buffer[(x + y * size) as usize];
+                assert!((value - expected).abs() < 1e-5, "{:?}: {:?} != {:?}", p, value, expected);
+                assert!((prepared.buffer[(x + y * size) as usize] - expected).abs() < 1e-5);
+            }
+        }
+
+        let msdf = msdf_generate(size, size, 0, spread, &circle);
+        assert!(msdf.buffer.iter().all(|v| !v.is_nan()));
+
+        // Half circle closed by a line
+        let half = Line::Arc { center: vec2(0.5, 0.5), radius: vec2(0.3, 0.3), rotation: 0.0, start_angle: PI, sweep_angle: PI };
+        let shape = Shape::from_lines(&[half]);
+        assert_eq!(shape.len(), 2);
+        assert!(shape.contours().all(|contour| contour.is_closed() && contour.winding() == 1));
+
+        // Circle made of two half arcs. The vertices shared by the arcs must be counted once by the scanline going through them
+        let mut circle = Shape::new();
+        circle.move_to(0.2, 0.5)
+            .arc_to(vec2(0.3, 0.3), 0.0, true, true, 0.8, 0.5)
+            .arc_to(vec2(0.3, 0.3), 0.0, true, true, 0.2, 0.5)
+            .close();
+        assert!(!contains(vec2(0.05, 0.5), &circle) && !contains(vec2(0.95, 0.5), &circle));
+        assert!(contains(vec2(0.5, 0.5), &circle) && contains(vec2(0.21, 0.5), &circle));
+
+        let size = 63;
+        let sdf = sdf_generate(size, size, 0, spread, &circle);
+        let y = size / 2;
+        for x in 0..size {
+            let p = vec2((x as f32 + 0.5) / size as f32, (y as f32 + 0.5) / size as f32);
+            let expected = (0.5 + (0.3 - (p - vec2(0.5, 0.5)).length()) * spread).clamp(0.0, 1.0);
+            let value = sdf.buffer[(x + y * size) as usize];
+            assert!((value - expected).abs() < 1e-5, "{:?}: {:?} != {:?}", p, value, expected);
+        }
+    }
+
     #[test]
     fn test_intersection() {
         let intersection_1 = |line: &Line, y: f32| {
@@ -631,6 +1990,602 @@ mod tests {
         sdf_render_to_file("test_outputs/stretch_render.png", render_scale, 0.5, 0.02, &sdf).unwrap();
     }
 
+    #[test]
+    fn test_threads() {
+        let shapes: [&[Line]; 2] = [
+            &[
+                Line::Line { start: vec2(0.0, 0.0), end: vec2(1.0, 0.0) },
+                Line::Line { start: vec2(1.0, 0.0), end: vec2(1.0, 1.0) },
+                Line::Line { start: vec2(1.0, 1.0), end: vec2(0.0, 1.0) },
+                Line::Line { start: vec2(0.0, 1.0), end: vec2(0.0, 0.0) },
+                Line::Line { start: vec2(0.5, 0.25), end: vec2(0.25, 0.75) },
+                Line::Line { start: vec2(0.25, 0.75), end: vec2(0.75, 0.75) },
+                Line::Line { start: vec2(0.75, 0.75), end: vec2(0.5, 0.25) },
+            ],
+            &[
+                Line::Curve { start: vec2(0.1, 0.9), end: vec2(0.9, 0.9), first_control: vec2(0.4, 0.5), second_control: vec2(0.6, 1.1) },
+                Line::Line { start: vec2(0.9, 0.9), end: vec2(0.9, 0.1) },
+                Line::Curve { start: vec2(0.9, 0.1), end: vec2(0.1, 0.1), first_control: vec2(0.6, 0.5), second_control: vec2(0.2, -0.1) },     
+                Line::Line { start: vec2(0.1, 0.1), end: vec2(0.1, 0.9) },
+            ]
+        ];
+
+        for lines in shapes {
+            let serial = sdf_generate(37, 23, 2, 8.0, lines);
+            for threads in [0, 2, 3, 7, 64] {
+                let settings = SdfSettings { threads, ..Default::default() };
+                let threaded = sdf_generate_with_settings(37, 23, 2, 8.0, lines, settings);
+                assert_eq!(serial.buffer, threaded.buffer, "threads: {:?}", threads);
+            }
+        }
+    }
+
+    #[test]
+    fn test_prepared_shape() {
+        // A star with a lot of points and a round hole
+        let points = 150;
+        let mut lines = Vec::new();
+        let star_point = |i: usize| {
+            let angle = (i as f32 / points as f32) * std::f32::consts::TAU;
+            let radius = if i % 2 == 0 { 0.45 } else { 0.35 };
+            vec2(0.5 + angle.cos() * radius, 0.5 + angle.sin() * radius)
+        };
+
+        for i in 0..points {
+            lines.push(Line::Line { start: star_point(i), end: star_point((i + 1) % points) });
+        }
+
+        lines.push(Line::Quad { start: vec2(0.4, 0.5), end: vec2(0.6, 0.5), control: vec2(0.5, 0.3) });
+        lines.push(Line::Curve { start: vec2(0.6, 0.5), end: vec2(0.4, 0.5), first_control: vec2(0.6, 0.65), second_control: vec2(0.4, 0.65) });
+
+        let shape = PreparedShape::new(&lines);
+        assert_eq!(shape.lines().len(), lines.len());
+
+        for (width, height, padding, spread) in [(64, 64, 0, 15.0), (48, 20, 3, 8.0), (33, 47, 2, 40.0), (16, 16, 1, 0.5)] {
+            let expected = sdf_generate(width, height, padding, spread, &lines);
+            let prepared = shape.sdf_generate(width, height, padding, spread);
+            assert_eq!(expected.buffer, prepared.buffer, "{:?}", (width, height, padding, spread));
+
+            let settings = SdfSettings { threads: 3, ..Default::default() };
+            let prepared = shape.sdf_generate_with_settings(width, height, padding, spread, settings);
+            assert_eq!(expected.buffer, prepared.buffer, "{:?}", (width, height, padding, spread));
+        }
+
+        let empty = PreparedShape::new(&[]);
+        assert_eq!(empty.sdf_generate(8, 8, 0, 8.0).buffer, sdf_generate(8, 8, 0, 8.0, &[]).buffer);
+    }
+
+    #[test]
+    fn test_fill_rule() {
+        let square = |x0: f32, y0: f32, x1: f32, y1: f32| [
+            Line::Line { start: vec2(x0, y0), end: vec2(x1, y0) },
+            Line::Line { start: vec2(x1, y0), end: vec2(x1, y1) },
+            Line::Line { start: vec2(x1, y1), end: vec2(x0, y1) },
+            Line::Line { start: vec2(x0, y1), end: vec2(x0, y0) },
+        ];
+
+        let mut out = [0.0; 3];
+        let mut directions = [0; 3];
+        let [top, right, bottom, left] = square(0.2, 0.2, 0.8, 0.8);
+        assert_eq!(right.intersections_with_direction(0.5, &mut out, &mut directions), 1);
+        assert_eq!((out[0], directions[0]), (0.8, 1));
+        assert_eq!(left.intersections_with_direction(0.5, &mut out, &mut directions), 1);
+        assert_eq!((out[0], directions[0]), (0.2, -1));
+
+        let quad = Line::Quad { start: vec2(0.2, 0.8), end: vec2(0.8, 0.8), control: vec2(0.5, 0.2) };
+        assert_eq!(quad.intersections_with_direction(0.6, &mut out, &mut directions), 2);
+        assert_eq!(directions[0] + directions[1], 0);
+
+        // Two overlapping squares with the same direction
+        let mut lines = square(0.1, 0.1, 0.6, 0.6).to_vec();
+        lines.extend_from_slice(&square(0.4, 0.4, 0.9, 0.9));
+
+        // A square with a hole in the opposite direction
+        let mut hole = square(0.1, 0.1, 0.9, 0.9).to_vec();
+        hole.extend_from_slice(&square(0.7, 0.3, 0.3, 0.7));
+
+        let non_zero = SdfSettings { fill_rule: FillRule::NonZero, ..Default::default() };
+        let value = |sdf: &SdfRaster, x: u32, y: u32| sdf.buffer[(x + (y * sdf.width)) as usize];
+
+        let even_odd_sdf = sdf_generate(64, 64, 0, 8.0, &lines);
+        let non_zero_sdf = sdf_generate_with_settings(64, 64, 0, 8.0, &lines, non_zero);
+        assert!(value(&even_odd_sdf, 32, 32) < 0.5);
+        assert!(value(&non_zero_sdf, 32, 32) > 0.5);
+        assert!(value(&non_zero_sdf, 16, 16) > 0.5 && value(&non_zero_sdf, 48, 48) > 0.5);
+        assert!(value(&non_zero_sdf, 48, 16) < 0.5 && value(&non_zero_sdf, 16, 48) < 0.5);
+
+        let prepared_sdf = PreparedShape::new(&lines).sdf_generate_with_settings(64, 64, 0, 8.0, non_zero);
+        assert_eq!(prepared_sdf.buffer, non_zero_sdf.buffer);
+
+        let even_odd_sdf = sdf_generate(64, 64, 0, 8.0, &hole);
+        let non_zero_sdf = sdf_generate_with_settings(64, 64, 0, 8.0, &hole, non_zero);
+        assert_eq!(even_odd_sdf.buffer, non_zero_sdf.buffer);
+        assert!(value(&non_zero_sdf, 32, 32) < 0.5);
+
+        #[cfg(feature="export")]
+        {
+            let sdf = sdf_generate_with_settings(64, 64, 0, 8.0, &lines, non_zero);
+            sdf_to_file("test_outputs/non_zero.png", &sdf).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_scanline_vertices() {
+        // Shapes with vertices, horizontal lines and curve extrema exactly on the center of the pixel rows
+        let row = |y: u32| (y as f32 + 0.5) / 64.0;
+        let diamond = [
+            Line::Line { start: vec2(0.5, row(8)), end: vec2(0.8, row(32)) },
+            Line::Line { start: vec2(0.8, row(32)), end: vec2(0.5, row(56)) },
+            Line::Line { start: vec2(0.5, row(56)), end: vec2(0.2, row(32)) },
+            Line::Line { start: vec2(0.2, row(32)), end: vec2(0.5, row(8)) },
+        ];
+
+        let quad_dome = [
+            Line::Quad { start: vec2(0.2, row(40)), end: vec2(0.8, row(40)), control: vec2(0.5, 2.0 * row(16) - row(40)) },
+            Line::Line { start: vec2(0.8, row(40)), end: vec2(0.2, row(40)) },
+        ];
+
+        let top = (4.0 * row(16) - row(40)) / 3.0;
+        let cubic_dome = [
+            Line::Curve { start: vec2(0.2, row(40)), end: vec2(0.8, row(40)), first_control: vec2(0.2, top), second_control: vec2(0.8, top) },
+            Line::Line { start: vec2(0.8, row(40)), end: vec2(0.2, row(40)) },
+        ];
+
+        let mut out = [0.0; 3];
+        let mut directions = [0; 3];
+        assert_eq!(diamond[0].intersections_with_direction(row(8), &mut out, &mut directions), 1);
+        assert_eq!(diamond[0].intersections_with_direction(row(32), &mut out, &mut directions), 0);
+        assert_eq!(diamond[1].intersections_with_direction(row(32), &mut out, &mut directions), 1);
+        assert_eq!(quad_dome[1].intersections_with_direction(row(40), &mut out, &mut directions), 0);
+
+        for lines in [&diamond[..], &quad_dome[..], &cubic_dome[..]] {
+            for fill_rule in [FillRule::EvenOdd, FillRule::NonZero] {
+                for y in [row(8), row(16), row(32), row(40), row(56)] {
+                    let scanline = scanline(y, lines, fill_rule);
+                    assert_eq!(scanline.intersections.len() % 2, 0, "{:?}", (y, fill_rule));
+                    assert!(!scanline_scan(&scanline, 0.1), "{:?}", (y, fill_rule));
+                    assert!(!scanline_scan(&scanline, 0.9), "{:?}", (y, fill_rule));
+                }
+
+                // No pixel far from the shape can be flipped
+                let settings = SdfSettings { fill_rule, ..Default::default() };
+                let sdf = sdf_generate_with_settings(64, 64, 0, 4.0, lines, settings);
+                for y in 0..64 {
+                    for x in [0, 1, 2, 61, 62, 63] {
+                        assert!(sdf.buffer[(x + (y * 64)) as usize] < 0.5, "{:?}", (x, y, fill_rule));
+                    }
+                }
+
+                let center = sdf.buffer[32 + (32 * 64)];
+                assert!(center > 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pixel_units() {
+        let lines = [
+            Line::Line { start: vec2(0.25, 0.25), end: vec2(0.75, 0.25) },
+            Line::Line { start: vec2(0.75, 0.25), end: vec2(0.75, 0.75) },
+            Line::Line { start: vec2(0.75, 0.75), end: vec2(0.25, 0.75) },
+            Line::Line { start: vec2(0.25, 0.75), end: vec2(0.25, 0.25) },
+        ];
+
+        let spread = 0.1;
+        let settings = SdfSettings { units: DistanceUnits::Pixels, ..Default::default() };
+        let value = |sdf: &SdfRaster, x: u32, y: u32| sdf.buffer[(x + (y * sdf.width)) as usize];
+
+        // 2.5 pixels away from the left edge and from the top edge
+        for (width, height) in [(64, 32), (128, 64), (64, 64)] {
+            let sdf = sdf_generate_with_settings(width, height, 0, spread, &lines, settings);
+            let left = value(&sdf, (width / 4) - 3, height / 2);
+            let top = value(&sdf, width / 2, (height / 4) - 3);
+            let expected = 0.5 - (2.5 * spread);
+            assert!((left - expected).abs() < 1e-4, "{:?}", (width, height, left));
+            assert!((top - expected).abs() < 1e-4, "{:?}", (width, height, top));
+
+            let inside = value(&sdf, (width / 4) + 2, height / 2);
+            assert!((inside - (0.5 + (2.5 * spread))).abs() < 1e-4, "{:?}", (width, height, inside));
+
+            let prepared = PreparedShape::new(&lines).sdf_generate_with_settings(width, height, 0, spread, settings);
+            assert_eq!(prepared.buffer, sdf.buffer);
+        }
+
+        let sdf = sdf_generate_with_settings(64, 32, 4, spread, &lines, settings);
+        let prepared = PreparedShape::new(&lines).sdf_generate_with_settings(64, 32, 4, spread, settings);
+        assert_eq!(prepared.buffer, sdf.buffer);
+
+        #[cfg(feature="export")]
+        {
+            let sdf = sdf_generate_with_settings(64, 32, 0, spread, &lines, settings);
+            sdf_to_file("test_outputs/pixel_units.png", &sdf).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_spread() {
+        let lines = [
+            Line::Line { start: vec2(0.25, 0.25), end: vec2(0.75, 0.25) },
+            Line::Line { start: vec2(0.75, 0.25), end: vec2(0.75, 0.75) },
+            Line::Line { start: vec2(0.75, 0.75), end: vec2(0.25, 0.75) },
+            Line::Line { start: vec2(0.25, 0.75), end: vec2(0.25, 0.25) },
+        ];
+
+        let sdf = sdf_generate(64, 32, 0, 4.0, &lines);
+        assert_eq!(sdf.pixel_range, vec2(16.0, 8.0));
+        assert_eq!(sdf_to_bitmap(&sdf).pixel_range, sdf.pixel_range);
+
+        // A pixel range is the same as a spread in pixel units
+        let range = Spread::Pixels(8.0);
+        let settings = SdfSettings { units: DistanceUnits::Pixels, ..Default::default() };
+        let sdf = sdf_generate(64, 32, 0, range, &lines);
+        assert_eq!(sdf.pixel_range, vec2(8.0, 8.0));
+        assert_eq!(sdf.buffer, sdf_generate_with_settings(64, 32, 0, 1.0 / 8.0, &lines, settings).buffer);
+        assert_eq!(sdf.buffer, PreparedShape::new(&lines).sdf_generate(64, 32, 0, range).buffer);
+
+        // Decode the distance of a pixel 2.5 pixels away from the edge
+        let value = sdf.buffer[13 + (16 * 64)];
+        assert!(((value - 0.5) * sdf.pixel_range[0] + 2.5).abs() < 1e-4);
+
+        for sdf in [msdf_generate(64, 32, 0, range, &lines), mtsdf_generate(64, 32, 0, range, &lines)] {
+            assert_eq!(sdf.pixel_range, vec2(8.0, 8.0));
+            let channels = sdf.channels as usize;
+            let value = msdf::median([sdf.buffer[(13 + (16 * 64)) * channels], sdf.buffer[(13 + (16 * 64)) * channels + 1], sdf.buffer[(13 + (16 * 64)) * channels + 2]]);
+            assert!(((value - 0.5) * sdf.pixel_range[0] + 2.5).abs() < 1e-4);
+        }
+
+        let mask: Vec<u8> = (0..64*32).map(|i| if (16..48).contains(&(i % 64)) && (8..24).contains(&(i / 64)) { 255 } else { 0 }).collect();
+        let sdf = sdf_from_mask(64, 32, range, &mask);
+        assert_eq!(sdf.pixel_range, vec2(8.0, 8.0));
+        let value = sdf.buffer[13 + (16 * 64)];
+        assert!(((value - 0.5) * sdf.pixel_range[0] + 2.5).abs() < 1e-4);
+
+        #[cfg(feature="font")]
+        {
+            let font_data = std::fs::read("./test_fixtures/Questrial-Regular.ttf").expect("Failed to read font file");
+            let font = Font::from_bytes(font_data.as_slice(), Default::default()).expect("Failed to parse font file");
+            let px = 512.0;
+            for padding in [0, 8, 32] {
+                // The stem of 'l' is a rectangle. Decode the distance at the center of the row in the middle of the stem
+                let (metrics, sdf) = font.sdf_generate(px, padding, Spread::em(0.5, px), 'l').unwrap();
+                assert_eq!(metrics.pixel_range, sdf.pixel_range);
+                let (width, height) = (sdf.width as usize, sdf.height as usize);
+                let x = width / 2;
+                let value = sdf.buffer[x + ((height / 2) * width)];
+                let distance = (value - 0.5) * metrics.em_range[0];
+
+                // Position of the pixel in the outline, in em units
+                let stem_width = metrics.bounds.width / px;
+                let pad = padding as f32 / width as f32;
+                let u = ((x as f32 + 0.5) / width as f32) * (1.0 + (2.0 * pad)) - pad;
+                let expected = (u * stem_width).min((1.0 - u) * stem_width);
+                assert!((distance - expected).abs() < 1e-3, "{} {} {}", padding, distance, expected);
+
+                // The em range averages the two axes
+                assert!(((metrics.em_range[0] + metrics.em_range[1]) - 1.0).abs() < 1e-4);
+
+                let (msdf_metrics, msdf) = font.msdf_generate(px, padding, Spread::em(0.5, px), 'l').unwrap();
+                assert_eq!((msdf_metrics.pixel_range, msdf_metrics.em_range), (msdf.pixel_range, metrics.em_range));
+            }
+
+            assert_eq!(font.metrics('a', 32.0).unwrap().pixel_range, vec2(0.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn test_signed_distance() {
+        let lines = [
+            Line::Line { start: vec2(0.25, 0.25), end: vec2(0.75, 0.25) },
+            Line::Line { start: vec2(0.75, 0.25), end: vec2(0.75, 0.75) },
+            Line::Line { start: vec2(0.75, 0.75), end: vec2(0.25, 0.75) },
+            Line::Line { start: vec2(0.25, 0.75), end: vec2(0.25, 0.25) },
+        ];
+
+        let settings = SdfSettings { units: DistanceUnits::Pixels, ..Default::default() };
+        let distances = signed_distance_generate(64, 64, 0, &lines, settings);
+        let distance = |x: u32, y: u32| distances.buffer[(x + (y * 64)) as usize];
+        assert_eq!((distances.width, distances.height, distances.units), (64, 64, DistanceUnits::Pixels));
+        assert!((distance(32, 32) - 15.5).abs() < 1e-4);
+        assert!((distance(5, 32) + 10.5).abs() < 1e-4);
+        assert!((distance(0, 0) + (15.5f32 * 15.5 * 2.0).sqrt()).abs() < 1e-4);
+
+        let distances = signed_distance_generate(64, 32, 2, &lines, Default::default());
+        assert_eq!(distances.units, DistanceUnits::Normalized);
+        assert!(distances.buffer[32 + (16 * 64)] > 0.2);
+
+        // Same output as `sdf_generate`
+        for (width, height, padding, units) in [(64, 64, 0, DistanceUnits::Pixels), (64, 32, 2, DistanceUnits::Normalized), (17, 33, 1, DistanceUnits::Pixels)] {
+            let settings = SdfSettings { units, threads: 2, ..Default::default() };
+            let distances = signed_distance_generate(width, height, padding, &lines, settings);
+            for spread in [0.1, 4.0, 15.0] {
+                let expected = sdf_generate_with_settings(width, height, padding, spread, &lines, settings);
+                let sdf = signed_distance_to_sdf(&distances, spread);
+                assert_eq!(sdf.buffer, expected.buffer);
+                assert_eq!(sdf.pixel_range, expected.pixel_range);
+            }
+        }
+    }
+
+    #[test]
+    fn test_point_queries() {
+        let mut shape = shapes::star(vec2(0.45, 0.5), 0.4, 0.2, 5, 0.3);
+        shape.move_to(0.45, 0.45).quad_to(0.6, 0.5, 0.45, 0.55).line_to(0.45, 0.55).close();
+        let mut overlapping = shapes::circle(vec2(0.4, 0.5), 0.3);
+        overlapping.move_to(0.5, 0.2).line_to(0.9, 0.5).line_to(0.5, 0.8).close();
+
+        // Same values as the pixels of the sdf
+        for (lines, fill_rule) in [(&shape, FillRule::EvenOdd), (&overlapping, FillRule::EvenOdd), (&overlapping, FillRule::NonZero)] {
+            for (width, height) in [(32, 32), (40, 24)] {
+                let spread = 4.0;
+                let settings = SdfSettings { fill_rule, ..Default::default() };
+                let sdf = sdf_generate_with_settings(width, height, 0, spread, lines, settings);
+                for y in 0..height {
+                    for x in 0..width {
+                        let p = vec2((x as f32 + 0.5) * (1.0 / width as f32), (y as f32 + 0.5) * (1.0 / height as f32));
+                        let distance = signed_distance_with_fill_rule(p, lines, fill_rule);
+                        assert_eq!(sdf_value(distance, spread), sdf.buffer[(x + y * width) as usize], "{:?}", p);
+                        assert_eq!(contains_with_fill_rule(p, lines, fill_rule), !distance.is_sign_negative());
+                    }
+                }
+            }
+        }
+
+        // The default fill rule is the same as `sdf_generate`
+        let p = vec2(0.6, 0.5);
+        assert!(!contains(p, &overlapping) && contains_with_fill_rule(p, &overlapping, FillRule::NonZero));
+        assert_eq!(signed_distance(p, &overlapping), signed_distance_with_fill_rule(p, &overlapping, FillRule::EvenOdd));
+
+        // Lines that cannot be rendered are ignored
+        let square = shapes::rectangle(vec2(0.25, 0.25), vec2(0.75, 0.75));
+        let mut degenerate = square.lines().to_vec();
+        degenerate.push(Line::Line { start: vec2(0.5, 0.5), end: vec2(0.5, 0.5) });
+        degenerate.push(Line::Line { start: vec2(f32::NAN, 0.5), end: vec2(0.5, 0.5) });
+        assert_eq!(signed_distance(vec2(0.5, 0.5), &degenerate), 0.25);
+        assert_eq!(signed_distance(vec2(0.0, 0.5), &degenerate), -0.25);
+        assert!(contains(vec2(0.5, 0.5), &degenerate));
+        assert_eq!(signed_distance(vec2(0.5, 0.5), &[]), -f32::MAX);
+    }
+
+    #[test]
+    fn test_generate_into() {
+        let lines = [
+            Line::Line { start: vec2(0.2, 0.2), end: vec2(0.8, 0.3) },
+            Line::Line { start: vec2(0.8, 0.3), end: vec2(0.5, 0.8) },
+            Line::Line { start: vec2(0.5, 0.8), end: vec2(0.2, 0.2) },
+        ];
+
+        let (width, height, stride) = (16, 8, 40);
+        for threads in [1, 3] {
+            let settings = SdfSettings { threads, ..Default::default() };
+            let expected = sdf_generate_with_settings(width, height, 1, 6.0, &lines, settings);
+            let expected_bitmap = sdf_to_bitmap(&expected);
+
+            // The sdf is in the last rows of the buffer, and the end of the buffer is the end of the sdf
+            let (x, y) = (5, 12);
+            let mut atlas = vec![-1.0f32; stride * 20];
+            let mut atlas_bytes = vec![1u8; ((y + height - 1) as usize * stride) + (x + width) as usize];
+
+            let range = sdf_generate_into(width, height, 1, 6.0, &lines, settings, SdfTarget { buffer: &mut atlas, stride, x, y });
+            assert_eq!(range, expected.pixel_range);
+            sdf_generate_into(width, height, 1, 6.0, &lines, settings, SdfTarget { buffer: &mut atlas_bytes, stride, x, y });
+
+            for ay in 0..20 {
+                for ax in 0..stride as u32 {
+                    let index = (ay as usize * stride) + ax as usize;
+                    let inside = ax >= x && ax < x + width && ay >= y && ay < y + height;
+                    match inside {
+                        true => {
+                            let sdf_index = ((ax - x) + ((ay - y) * width)) as usize;
+                            assert_eq!(atlas[index], expected.buffer[sdf_index]);
+                            assert_eq!(atlas_bytes[index], expected_bitmap.buffer[sdf_index]);
+                        },
+                        false => {
+                            assert_eq!(atlas[index], -1.0);
+                            if index < atlas_bytes.len() {
+                                assert_eq!(atlas_bytes[index], 1);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        #[cfg(feature="font")]
+        {
+            let font_data = std::fs::read("./test_fixtures/Questrial-Regular.ttf").expect("Failed to read font file");
+            let font = Font::from_bytes(font_data.as_slice(), Default::default()).expect("Failed to parse font file");
+            let (metrics, expected) = font.sdf_generate(32.0, 2, 6.0, 'g').unwrap();
+            let expected = sdf_to_bitmap(&expected);
+
+            let mut atlas = vec![0u8; 128 * 64];
+            let target = SdfTarget { buffer: &mut atlas, stride: 128, x: 60, y: 10 };
+            let atlas_metrics = font.sdf_generate_into(32.0, 2, 6.0, 'g', Default::default(), target).unwrap();
+            assert_eq!((atlas_metrics.width, atlas_metrics.height), (metrics.width, metrics.height));
+            for y in 0..metrics.height as usize {
+                let row = &atlas[((10 + y) * 128) + 60..((10 + y) * 128) + 60 + metrics.width as usize];
+                assert_eq!(row, &expected.buffer[y * metrics.width as usize..(y + 1) * metrics.width as usize]);
+            }
+
+            let target = SdfTarget { buffer: &mut atlas, stride: 128, x: 0, y: 0 };
+            assert!(font.sdf_generate_into(32.0, 2, 6.0, '\u{E000}', Default::default(), target).is_none());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_generate_into_overflow() {
+        let lines = [
+            Line::Line { start: vec2(0.2, 0.2), end: vec2(0.8, 0.3) },
+            Line::Line { start: vec2(0.8, 0.3), end: vec2(0.2, 0.2) },
+        ];
+
+        let mut atlas = vec![0.0f32; 32 * 32];
+        sdf_generate_into(16, 16, 0, 6.0, &lines, Default::default(), SdfTarget { buffer: &mut atlas, stride: 32, x: 20, y: 0 });
+    }
+
+    #[test]
+    fn test_regions() {
+        let square = |x0: f32, x1: f32| [
+            Line::Line { start: vec2(x0, 0.2), end: vec2(x1, 0.2) },
+            Line::Line { start: vec2(x1, 0.2), end: vec2(x1, 0.4) },
+            Line::Line { start: vec2(x1, 0.4), end: vec2(x0, 0.4) },
+            Line::Line { start: vec2(x0, 0.4), end: vec2(x0, 0.2) },
+        ];
+
+        let triangle = [
+            Line::Line { start: vec2(0.5, 0.6), end: vec2(0.9, 0.9) },
+            Line::Line { start: vec2(0.9, 0.9), end: vec2(0.1, 0.9) },
+            Line::Line { start: vec2(0.1, 0.9), end: vec2(0.5, 0.6) },
+        ];
+
+        let old_lines: Vec<Line> = square(0.1, 0.3).iter().chain(triangle.iter()).copied().collect();
+        let new_lines: Vec<Line> = triangle.iter().chain(square(0.15, 0.35).iter()).copied().collect();
+
+        for (spread, units) in [(8.0, DistanceUnits::Normalized), (0.25, DistanceUnits::Pixels)] {
+            let settings = SdfSettings { units, ..Default::default() };
+            assert_eq!(sdf_dirty_region(64, 64, 2, spread, settings, &old_lines, &old_lines), None);
+
+            // Only the pixels around the moved square need to be regenerated
+            let region = sdf_dirty_region(64, 64, 2, spread, settings, &old_lines, &new_lines).unwrap();
+            assert!(region.y + region.height < 48);
+
+            let mut sdf = sdf_generate_with_settings(64, 64, 2, spread, &old_lines, settings);
+            sdf_regenerate_region(&mut sdf, 2, spread, &new_lines, settings, region);
+            let expected = sdf_generate_with_settings(64, 64, 2, spread, &new_lines, settings);
+            assert!(sdf.buffer == expected.buffer);
+
+            // Tiles give the same output as the whole raster
+            let mut tiled = vec![0.0; 64 * 64];
+            for (x, y) in [(0, 0), (40, 0), (0, 24), (40, 24)] {
+                let region = SdfRegion { x, y, width: 40 - (x * 16 / 40), height: 24 + (y * 16 / 24) };
+                let tile = sdf_generate_region(64, 64, 2, spread, &new_lines, settings, region);
+                assert_eq!(tile.pixel_range, expected.pixel_range);
+                for ty in 0..tile.height {
+                    for tx in 0..tile.width {
+                        tiled[((x + tx) + ((y + ty) * 64)) as usize] = tile.buffer[(tx + (ty * tile.width)) as usize];
+                    }
+                }
+            }
+
+            assert!(tiled == expected.buffer);
+        }
+    }
+
+    #[test]
+    fn test_mask() {
+        // An anti-aliased and a binary circle mask, compared with the analytic sdf of the circle
+        let (width, height, spread) = (64, 48, 4.0);
+        let (center, radius) = (vec2(0.5, 0.5), 0.3);
+        let coverage = |x: u32, y: u32, samples: u32| {
+            let mut inside = 0;
+            for sy in 0..samples {
+                for sx in 0..samples {
+                    let px = (x as f32 + (sx as f32 + 0.5) / samples as f32) / width as f32;
+                    let py = (y as f32 + (sy as f32 + 0.5) / samples as f32) / height as f32;
+                    if (vec2(px, py) - center).length() < radius {
+                        inside += 1;
+                    }
+                }
+            }
+            ((inside as f32 / (samples * samples) as f32) * 255.0).round() as u8
+        };
+
+        for (samples, tolerance) in [(16, 0.02), (1, 0.05)] {
+            let mask: Vec<u8> = (0..width*height).map(|i| coverage(i % width, i / width, samples)).collect();
+            let sdf = sdf_from_mask(width, height, spread, &mask);
+            assert_eq!((sdf.width, sdf.height, sdf.channels, sdf.buffer.len()), (width, height, 1, (width * height) as usize));
+
+            for y in 0..height {
+                for x in 0..width {
+                    let p = vec2((x as f32 + 0.5) / width as f32, (y as f32 + 0.5) / height as f32);
+                    let expected = (0.5 + (radius - (p - center).length()) * spread).clamp(0.0, 1.0);
+                    let value = sdf.buffer[(x + (y * width)) as usize];
+                    assert!((value - expected).abs() < tolerance, "{:?}", (samples, x, y, value, expected));
+                }
+            }
+
+            #[cfg(feature="export")]
+            {
+                let name = format!("test_outputs/mask_{}.png", samples);
+                image::GrayImage::from_raw(width, height, mask.clone()).unwrap().save(&name).unwrap();
+                let loaded = sdf_from_mask_file(&name, spread).unwrap();
+                assert_eq!(loaded.buffer, sdf.buffer);
+            }
+        }
+
+        let empty = sdf_from_mask(8, 8, spread, &[0; 64]);
+        assert!(empty.buffer.iter().all(|&v| v == 0.0));
+        let full = sdf_from_mask(8, 8, spread, &[255; 64]);
+        assert!(full.buffer.iter().all(|&v| v == 1.0));
+    }
+
+    #[test]
+    fn test_msdf() {
+        let lines = [
+            Line::Line { start: vec2(0.2, 0.2), end: vec2(0.8, 0.2) },
+            Line::Line { start: vec2(0.8, 0.2), end: vec2(0.8, 0.8) },
+            Line::Line { start: vec2(0.8, 0.8), end: vec2(0.2, 0.8) },
+            Line::Line { start: vec2(0.2, 0.8), end: vec2(0.2, 0.2) },
+
+            Line::Line { start: vec2(0.5, 0.35), end: vec2(0.35, 0.65) },
+            Line::Line { start: vec2(0.35, 0.65), end: vec2(0.65, 0.65) },
+            Line::Line { start: vec2(0.65, 0.65), end: vec2(0.5, 0.35) },
+        ];
+
+        let size = 16;
+        let render_scale = 512.0 / (size as f32);
+
+        let sdf = sdf_generate(size, size, 0, 8.0, &lines);
+        let msdf = msdf_generate(size, size, 0, 8.0, &lines);
+        assert_eq!(msdf.channels, 3);
+        assert_eq!(msdf.buffer.len(), (size * size * 3) as usize);
+
+        // The inside of the msdf must match the sdf
+        for (i, &value) in sdf.buffer.iter().enumerate() {
+            let median = msdf::median([msdf.buffer[i*3], msdf.buffer[i*3+1], msdf.buffer[i*3+2]]);
+            assert_eq!(value > 0.5, median > 0.5, "pixel: {:?} sdf: {:?} msdf: {:?}", i, value, median);
+        }
+
+        // Corners stay sharp when magnified
+        assert!(sdf_sample(&sdf, 0.795, 0.795) < 0.5);
+        assert!(sdf_sample(&msdf, 0.795, 0.795) > 0.5);
+        assert!(sdf_sample(&msdf, 0.805, 0.805) < 0.5);
+
+        #[cfg(feature="export")]
+        sdf_to_file("test_outputs/msdf.png", &msdf).unwrap();
+
+        #[cfg(feature="render")]
+        #[cfg(feature="export")]
+        sdf_render_to_file("test_outputs/msdf_render.png", render_scale, 0.5, 0.02, &msdf).unwrap();
+    }
+
+    #[test]
+    fn test_mtsdf() {
+        let lines = [
+            Line::Quad { start: vec2(0.1, 0.5), end: vec2(0.9, 0.5), control: vec2(0.5, 0.0) },
+            Line::Line { start: vec2(0.9, 0.5), end: vec2(0.9, 0.9) },
+            Line::Line { start: vec2(0.9, 0.9), end: vec2(0.1, 0.9) },
+            Line::Line { start: vec2(0.1, 0.9), end: vec2(0.1, 0.5) },
+        ];
+
+        let size = 24;
+        let padding = 2;
+        let spread = 6.0;
+
+        let sdf = sdf_generate(size, size, padding, spread, &lines);
+        let msdf = msdf_generate(size, size, padding, spread, &lines);
+        let mtsdf = mtsdf_generate(size, size, padding, spread, &lines);
+        assert_eq!(mtsdf.channels, 4);
+
+        for i in 0..(size * size) as usize {
+            assert_eq!(&mtsdf.buffer[i*4..i*4+3], &msdf.buffer[i*3..i*3+3]);
+            assert_eq!(mtsdf.buffer[i*4+3], sdf.buffer[i]);
+        }
+
+        #[cfg(feature="export")]
+        sdf_to_file("test_outputs/mtsdf.png", &mtsdf).unwrap();
+    }
+
     #[cfg(feature="font")]
     #[test]
     fn test_font_render() {
@@ -663,6 +2618,52 @@ mod tests {
         sdf_render_to_file("test_outputs/font_y_render.png", render_scale, 0.5, 0.02, &y_glyph_sdf).unwrap();
     }
 
+    #[cfg(feature="font")]
+    #[test]
+    fn test_font_batch() {
+        use std::fs;
+
+        let font_data = fs::read("./test_fixtures/Questrial-Regular.ttf").expect("Failed to read font file");
+        let font = Font::from_bytes(font_data.as_slice(), Default::default()).expect("Failed to parse font file");
+
+        let chars = ['a', 'b', 'W', '\u{0}', '8', 'y'];
+        let settings = SdfSettings { threads: 4, ..Default::default() };
+        let batch = font.sdf_generate_batch(24.0, 2, 8.0, &chars, settings);
+        assert_eq!(batch.len(), chars.len());
+
+        for (c, generated) in chars.iter().zip(batch.iter()) {
+            let expected = font.sdf_generate(24.0, 2, 8.0, *c);
+            match (expected, generated) {
+                (Some((_, expected)), Some((_, generated))) => assert_eq!(expected.buffer, generated.buffer),
+                (None, None) => {},
+                _ => panic!("Batch output for {:?} does not match", c)
+            }
+        }
+    }
+
+    #[cfg(feature="font")]
+    #[test]
+    fn test_font_msdf() {
+        use std::fs;
+
+        let font_data = fs::read("./test_fixtures/Questrial-Regular.ttf").expect("Failed to read font file");
+        let font = Font::from_bytes(font_data.as_slice(), Default::default()).expect("Failed to parse font file");
+
+        let px = 32.0;
+        let (metrics, glyph_msdf) = font.msdf_generate(px, 2, 12.0, 'k').unwrap();
+        assert_eq!(glyph_msdf.channels, 3);
+        assert_eq!((glyph_msdf.width, glyph_msdf.height), (metrics.width as u32, metrics.height as u32));
+
+        let render_scale = 512.0 / px;
+
+        #[cfg(feature="export")]
+        sdf_to_file("test_outputs/font_k_msdf.png", &glyph_msdf).unwrap();
+
+        #[cfg(feature="render")]
+        #[cfg(feature="export")]
+        sdf_render_to_file("test_outputs/font_k_msdf_render.png", render_scale, 0.5, 0.02, &glyph_msdf).unwrap();
+    }
+
     #[cfg(feature="font")]
     #[test]
     fn test_font_fixed_height() {
@@ -676,17 +2677,401 @@ mod tests {
         assert_eq!(metrics.height, 100);
     }
 
-    // #[cfg(feature="path")]
-    // #[test]
-    // fn test_path() {
-    //     // From https://www.svgviewer.dev/s/13417/pet-14
-    //     let path = "
-    //         M 9.412959,0.00215164 C 8.1693111,0.14587964 7.4631991,1.5093558 7.3733471,2.6936522 7.2264421,3.7464942 7.6860601,5.0899481 8.7918901,5.3320382 9.750208,5.4175142 10.512633,4.5171926 10.825819,3.6583651 11.195679,2.5833906 11.206482,1.147845 10.309038,0.34250684 10.059753,0.12647084 9.739671,-0.00476426 9.412959,0.00215164 z 
-    //         m -5.0729339,0.056367 c -1.034807,0.074858 -1.5652,1.27744256 -1.493223,2.24276386 0.0088,1.2907239 0.717612,2.7424122 2.010186,3.0488129 0.963956,0.1042448 1.606661,-0.9181087 1.66656,-1.812036 0.123011,-1.3680543 -0.483132,-3.02530546 -1.855765,-3.43834986 -0.106687,-0.029465 -0.217332,-0.044089 -0.327758,-0.041191 z 
-    //         M 12.655043,4.2036246 c -1.388365,0.1017608 -2.266355,1.5813816 -2.326691,2.9184062 -0.07305,0.8707439 0.489487,1.9657608 1.436683,1.9057498 C 13.181597,8.8776256 14.035636,7.298817 14.00043,5.9270567 13.99133,5.1373276 13.490879,4.2365389 12.655043,4.2036246 z
-    //         M 0.01075614,6.0085263 c -0.12855096,1.481506 0.908149,3.1560203 2.41413596,3.2493013 0.912778,-0.06096 1.343188,-1.121093 1.238576,-1.9598358 C 3.5801081,6.0604403 2.7526501,4.7931558 1.6775691,4.6275979 0.60248914,4.4620399 -0.00628982,5.221568 0.01075614,6.0085263 z
-    //         M 7.1593651,7.2723472 c -1.411887,0.00329 -2.70277,0.9264694 -3.392913,2.1755314 -0.578235,0.9334254 -1.050947,1.9901564 -1.140853,3.1053424 0.01967,0.860048 0.840276,1.575842 1.663122,1.404534 1.173683,-0.152345 2.222485,-0.967418 3.44297,-0.8147 1.014283,0.08097 1.8507729,0.867482 2.8748439,0.856051 0.994345,0.03734 1.56796,-1.102139 1.188753,-1.994632 C 11.387234,10.726579 10.596107,9.6151936 9.746977,8.6142466 9.069618,7.87643 8.1824521,7.2396817 7.1593651,7.2723472 z
-    //     ";
-    // }
+    #[cfg(feature="path")]
+    #[test]
+    fn test_path() {
+        // From https://www.svgviewer.dev/s/13417/pet-14
+        let path = "
+            M 9.412959,0.00215164 C 8.1693111,0.14587964 7.4631991,1.5093558 7.3733471,2.6936522 7.2264421,3.7464942 7.6860601,5.0899481 8.7918901,5.3320382 9.750208,5.4175142 10.512633,4.5171926 10.825819,3.6583651 11.195679,2.5833906 11.206482,1.147845 10.309038,0.34250684 10.059753,0.12647084 9.739671,-0.00476426 9.412959,0.00215164 z 
+            m -5.0729339,0.056367 c -1.034807,0.074858 -1.5652,1.27744256 -1.493223,2.24276386 0.0088,1.2907239 0.717612,2.7424122 2.010186,3.0488129 0.963956,0.1042448 1.606661,-0.9181087 1.66656,-1.812036 0.123011,-1.3680543 -0.483132,-3.02530546 -1.855765,-3.43834986 -0.106687,-0.029465 -0.217332,-0.044089 -0.327758,-0.041191 z 
+            M 12.655043,4.2036246 c -1.388365,0.1017608 -2.266355,1.5813816 -2.326691,2.9184062 -0.07305,0.8707439 0.489487,1.9657608 1.436683,1.9057498 C 13.181597,8.8776256 14.035636,7.298817 14.00043,5.9270567 13.99133,5.1373276 13.490879,4.2365389 12.655043,4.2036246 z
+            M 0.01075614,6.0085263 c -0.12855096,1.481506 0.908149,3.1560203 2.41413596,3.2493013 0.912778,-0.06096 1.343188,-1.121093 1.238576,-1.9598358 C 3.5801081,6.0604403 2.7526501,4.7931558 1.6775691,4.6275979 0.60248914,4.4620399 -0.00628982,5.221568 0.01075614,6.0085263 z
+            M 7.1593651,7.2723472 c -1.411887,0.00329 -2.70277,0.9264694 -3.392913,2.1755314 -0.578235,0.9334254 -1.050947,1.9901564 -1.140853,3.1053424 0.01967,0.860048 0.840276,1.575842 1.663122,1.404534 1.173683,-0.152345 2.222485,-0.967418 3.44297,-0.8147 1.014283,0.08097 1.8507729,0.867482 2.8748439,0.856051 0.994345,0.03734 1.56796,-1.102139 1.188753,-1.994632 C 11.387234,10.726579 10.596107,9.6151936 9.746977,8.6142466 9.069618,7.87643 8.1824521,7.2396817 7.1593651,7.2723472 z
+        ";
+
+        let view_box = ViewBox::parse("-0.5 -0.5 15 15").unwrap();
+        let lines = parse_svg_path_normalized(path, view_box).unwrap();
+        assert_eq!(Shape::from_lines(&lines).contours().count(), 5);
+        assert_eq!(validate_lines(&lines), Vec::new());
+
+        let size: u32 = 64;
+        let render_scale = 512.0 / (size as f32);
+        let sdf = sdf_generate(size, size, 2, 8.0, &lines);
+
+        // Center of the big pad and the gap between the pads
+        assert!(sdf_sample(&sdf, 0.5, 0.8) > 0.5);
+        assert!(sdf_sample(&sdf, 0.5, 0.43) < 0.5);
+
+        #[cfg(feature="export")]
+        sdf_to_file("test_outputs/path.png", &sdf).unwrap();
+
+        #[cfg(feature="render")]
+        #[cfg(feature="export")]
+        sdf_render_to_file("test_outputs/path_render.png", render_scale, 0.5, 0.02, &sdf).unwrap();
+
+        // Commands, absolute and relative
+        let square = parse_svg_path("M 0.25 0.25 H 0.75 V 0.75 h -0.5 z m 0.25 0.25 l 0.125 0 0 0.125 Z").unwrap();
+        assert_eq!(square, vec![
+            Line::Line { start: vec2(0.25, 0.25), end: vec2(0.75, 0.25) },
+            Line::Line { start: vec2(0.75, 0.25), end: vec2(0.75, 0.75) },
+            Line::Line { start: vec2(0.75, 0.75), end: vec2(0.25, 0.75) },
+            Line::Line { start: vec2(0.25, 0.75), end: vec2(0.25, 0.25) },
+            Line::Line { start: vec2(0.5, 0.5), end: vec2(0.625, 0.5) },
+            Line::Line { start: vec2(0.625, 0.5), end: vec2(0.625, 0.625) },
+            Line::Line { start: vec2(0.625, 0.625), end: vec2(0.5, 0.5) },
+        ]);
+
+        let curves = parse_svg_path("M0 0C1 0 2 1 2 2s1 2 2 2Q5 4 5 5t1 1").unwrap();
+        assert_eq!(&curves[..4], &[
+            Line::Curve { start: vec2(0.0, 0.0), end: vec2(2.0, 2.0), first_control: vec2(1.0, 0.0), second_control: vec2(2.0, 1.0) },
+            Line::Curve { start: vec2(2.0, 2.0), end: vec2(4.0, 4.0), first_control: vec2(2.0, 3.0), second_control: vec2(3.0, 4.0) },
+            Line::Quad { start: vec2(4.0, 4.0), end: vec2(5.0, 5.0), control: vec2(5.0, 4.0) },
+            Line::Quad { start: vec2(5.0, 5.0), end: vec2(6.0, 6.0), control: vec2(5.0, 6.0) },
+        ]);
+
+        // Numbers without separators, and arcs drawing a circle
+        assert_eq!(parse_svg_path("M.5.5-1e1-1E+1").unwrap()[0], Line::Line { start: vec2(0.5, 0.5), end: vec2(-10.0, -10.0) });
+        let circle = parse_svg_path("M0.2 0.5a.3.3 0 1 1 .6 0a.3.3 0 1 1-.6 0z").unwrap();
+        assert_eq!(circle.len(), 2);
+        assert!((signed_area(&circle) - std::f32::consts::PI * 0.09).abs() < 1e-5);
+        for i in 0..=20 {
+            let p = circle[i % 2].point(i as f32 / 20.0);
+            assert!(((p - vec2(0.5, 0.5)).length() - 0.3).abs() < 1e-5);
+        }
+
+        // The row going through the joints of the arcs is inside between the joints only
+        assert!(!contains(vec2(0.05, 0.5), &circle) && contains(vec2(0.5, 0.5), &circle));
+        let (size, spread) = (63, 4.0);
+        let sdf = sdf_generate(size, size, 0, spread, &circle);
+        let y = size / 2;
+        for x in 0..size {
+            let p = vec2((x as f32 + 0.5) / size as f32, (y as f32 + 0.5) / size as f32);
+            let expected = (0.5 + (0.3 - (p - vec2(0.5, 0.5)).length()) * spread).clamp(0.0, 1.0);
+            assert!((sdf.buffer[(x + y * size) as usize] - expected).abs() < 1e-5, "{:?}", p);
+            assert_eq!(contains(p, &circle), (p - vec2(0.5, 0.5)).length() < 0.3, "{:?}", p);
+        }
+
+        // The radius of an arc too small to reach its end is scaled up
+        let arc = parse_svg_path("M 0 0 A 0.1 0.1 0 0 0 1 0").unwrap();
+        assert!((arc[0].point(0.5) - vec2(0.5, 0.5)).length() < 1e-5);
+
+        // Errors
+        assert_eq!(parse_svg_path("L 1 1"), Err(PathError::MissingMoveTo { position: 0 }));
+        assert_eq!(parse_svg_path("M 1 1 L 2"), Err(PathError::MissingNumber { position: 9 }));
+        assert_eq!(parse_svg_path("M 1 1 X"), Err(PathError::UnexpectedCharacter { position: 6, character: 'X' }));
+        assert_eq!(parse_svg_path("M 1 1 A 1 1 0 2 0 2 2"), Err(PathError::InvalidNumber { position: 14 }));
+        assert_eq!(parse_svg_path("M 1 1 Z 2"), Err(PathError::UnexpectedCharacter { position: 8, character: '2' }));
+        assert_eq!(ViewBox::parse("0 0 24"), None);
+        assert_eq!(ViewBox::parse("0,0,24,12"), Some(ViewBox { x: 0.0, y: 0.0, width: 24.0, height: 12.0 }));
+    }
+
+
+    #[cfg(feature="svg")]
+    #[test]
+    fn test_svg() {
+        let svg = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd">
+            <!-- Every element, in a 40x20 view box -->
+            <svg:svg xmlns:svg="http://www.w3.org/2000/svg" width="400px" height="200px" viewBox="0 0 40 20">
+                <svg:title>Test &amp; shapes</svg:title>
+                <svg:defs><svg:rect width="40" height="20"/></svg:defs>
+                <svg:g transform="translate(10, 0)" fill='black'>
+                    <svg:rect x="2" y="2" width="6" height="6"/>
+                    <svg:rect x="2" y="12" width="6" height="6" rx="1" ry="3"/>
+                    <svg:circle cx="15" cy="5" r="3"/>
+                    <svg:ellipse cx="15" cy="15" rx="3" ry="2"/>
+                </svg:g>
+                <svg:polygon points="32,2 38,2 35,8"/>
+                <svg:polyline points="32 12 38 12 35 18"/>
+                <svg:path d="M 1 1 h 2 v 2 z" fill="none"/>
+                <svg:g style="display: none"><svg:path d="M 1 1 h 2 v 2 z"/></svg:g>
+                <svg:path d="M 1 1 h 2 v 2 z" style="fill:none;stroke:black"/>
+                <svg:path d="M 5 15 h -2 v -2 z"/>
+            </svg:svg>
+        "#;
+
+        let lines = parse_svg(svg).unwrap();
+        let shape = Shape::from_lines(&lines);
+        assert_eq!(shape.contours().count(), 7);
+        assert_eq!(validate_lines(&lines), Vec::new());
+        assert!(shape.contours().all(|contour| contour.signed_area() > 0.0));
+
+        let sdf = sdf_from_svg(svg, 64, 32, 0, 8.0).unwrap();
+
+        #[cfg(feature="export")]
+        sdf_to_file("test_outputs/svg.png", &sdf).unwrap();
+
+        for (x, y) in [(15.0, 5.0), (25.0, 15.0), (35.0, 5.0), (35.0, 14.0), (4.0, 14.5), (17.0, 17.0)] {
+            assert!(sdf_sample(&sdf, x / 40.0, y / 20.0) > 0.5, "{} {}", x, y);
+        }
+
+        for (x, y) in [(2.0, 2.0), (12.1, 12.1), (30.0, 10.0), (2.0, 10.0), (12.1, 2.1)] {
+            assert!(sdf_sample(&sdf, x / 40.0, y / 20.0) < 0.5, "{} {}", x, y);
+        }
+
+        // Transforms, and arcs transformed by a skew and a mirror
+        let svg = r#"<svg width="10" height="10">
+            <g transform="rotate(90 5 5)"><rect x="1" y="4" width="2" height="2"/></g>
+            <circle cx="5" cy="5" r="1" transform="translate(5 5) skewX(30) scale(-2 1) translate(-5 -5)"/>
+        </svg>"#;
+
+        let lines = parse_svg(svg).unwrap();
+        assert_eq!(lines.len(), 5);
+        for line in &lines[..4] {
+            let p = line.start();
+            assert!(p[0] > 0.4 - 1e-6 && p[0] < 0.6 + 1e-6 && p[1] > 0.1 - 1e-6 && p[1] < 0.3 + 1e-6, "{:?}", p);
+        }
+
+        assert!(lines[4].signed_area() > 0.0);
+        let skew = 30f32.to_radians().tan();
+        for i in 0..=20 {
+            let p = lines[4].point(i as f32 / 20.0) * 10.0 - vec2(5.0, 5.0);
+            let (x, y) = ((p[0] - skew * p[1]) / -2.0, p[1]);
+            assert!(((x*x + y*y).sqrt() - 1.0).abs() < 1e-4, "{:?}", p);
+        }
+
+        // Fill rules
+        let square = "M 1 1 H 9 V 9 H 1 Z M 3 3 H 7 V 7 H 3 Z";
+        let svg = format!(r#"<svg viewBox="0 0 10 10"><path d="{}"/></svg>"#, square);
+        let sdf = sdf_from_svg(&svg, 32, 32, 0, 8.0).unwrap();
+        assert!(sdf_sample(&sdf, 0.5, 0.5) > 0.5);
+
+        let svg = format!(r#"<svg viewBox="0 0 10 10"><g fill-rule="evenodd"><path d="{}"/></g></svg>"#, square);
+        let sdf = sdf_from_svg(&svg, 32, 32, 0, 8.0).unwrap();
+        assert!(sdf_sample(&sdf, 0.5, 0.5) < 0.5);
+        assert!(sdf_sample(&sdf, 0.2, 0.5) > 0.5);
+
+        // Transform functions
+        let rect = |transform: &str| parse_svg(&format!(r#"<svg viewBox="0 0 8 8"><rect width="1" height="1" transform="{}"/></svg>"#, transform));
+        let start = |transform: &str| rect(transform).unwrap()[1].start() * 8.0;
+        assert_eq!(start("matrix(1 0 0 1 2 3)"), vec2(3.0, 3.0));
+        assert_eq!(start("translate(2)"), vec2(3.0, 0.0));
+        assert_eq!(start("scale(2) translate(1,1)"), vec2(4.0, 2.0));
+        assert_eq!(start("translate(1,1)scale(2 4)"), vec2(3.0, 1.0));
+        assert_eq!(start("skewY(45)"), vec2(1.0, 1.0));
+
+        // Nested viewports
+        let nested = |attributes: &str| {
+            let svg = format!(r#"<svg viewBox="0 0 8 8"><g transform="translate(1 1)"><svg {}><rect width="2" height="2"/></svg></g></svg>"#, attributes);
+            let lines = parse_svg(&svg).unwrap();
+            (lines[0].start() * 8.0, lines[2].start() * 8.0)
+        };
+
+        assert_eq!(nested(""), (vec2(1.0, 1.0), vec2(3.0, 3.0)));
+        assert_eq!(nested(r#"x="1" y="2""#), (vec2(2.0, 3.0), vec2(4.0, 5.0)));
+        assert_eq!(nested(r#"x="1" y="2" width="4" height="4" viewBox="1 1 2 2""#), (vec2(0.0, 1.0), vec2(4.0, 5.0)));
+        assert_eq!(nested(r#"width="4" height="2" viewBox="0 0 2 2""#), (vec2(2.0, 1.0), vec2(4.0, 3.0)));
+        assert_eq!(nested(r#"width="4" height="2" viewBox="0 0 2 2" preserveAspectRatio="xMinYMax""#), (vec2(1.0, 1.0), vec2(3.0, 3.0)));
+        assert_eq!(nested(r#"width="4" height="2" viewBox="0 0 2 2" preserveAspectRatio="none""#), (vec2(1.0, 1.0), vec2(5.0, 3.0)));
+        assert_eq!(nested(r#"width="4" height="2" viewBox="0 0 2 2" preserveAspectRatio="xMidYMax slice""#), (vec2(1.0, -1.0), vec2(5.0, 3.0)));
+        assert_eq!(nested(r#"viewBox="0 0 4 4""#), (vec2(1.0, 1.0), vec2(5.0, 5.0)));
+
+        // Errors
+        assert_eq!(parse_svg("<svg viewBox='0 0 1 1'><svg viewBox='0 0 1 1' preserveAspectRatio='xMidYMid fit'/></svg>"), Err(SvgError::InvalidAttribute { position: 67 }));
+        assert_eq!(parse_svg("<html></html>"), Err(SvgError::InvalidDocument { position: 0 }));
+        assert_eq!(parse_svg("<svg viewBox='0 0 1 1'><g></svg>"), Err(SvgError::InvalidDocument { position: 26 }));
+        assert_eq!(parse_svg("<svg viewBox='0 0 1 1'>"), Err(SvgError::InvalidDocument { position: 23 }));
+        assert_eq!(parse_svg("<svg viewBox='0 0 1 1'><g x=1/></svg>"), Err(SvgError::InvalidDocument { position: 28 }));
+        assert_eq!(parse_svg("<svg viewBox='0 0 1 1'/><svg/>"), Err(SvgError::InvalidDocument { position: 24 }));
+        assert_eq!(parse_svg("<svg></svg>"), Err(SvgError::MissingViewBox));
+        assert_eq!(parse_svg("<svg viewBox='0 0 0 1'/>"), Err(SvgError::InvalidAttribute { position: 14 }));
+        assert_eq!(rect("rotate(1 2)"), Err(SvgError::InvalidAttribute { position: 61 }));
+        assert_eq!(parse_svg("<svg width='1em' height='1'/>"), Err(SvgError::InvalidAttribute { position: 12 }));
+        assert_eq!(
+            parse_svg("<svg viewBox='0 0 1 1'><path d='M 0 0 L 1'/></svg>"),
+            Err(SvgError::InvalidPath { position: 32, error: PathError::MissingNumber { position: 9 } })
+        );
+    }
+
+    /// Check every pixel of `sdf` against the signed distance `distance` (positive inside) at its center.
+    /// With `overlap`, the values inside the shape only need to be between the edge and the expected value (ex: where strokes overlap).
+    fn check_sdf(name: &str, sdf: &SdfRaster, spread: f32, tolerance: f32, overlap: bool, distance: &dyn Fn(Vec2) -> f32) {
+        let (width, height) = (sdf.width, sdf.height);
+        for y in 0..height {
+            for x in 0..width {
+                let p = vec2((x as f32 + 0.5) / width as f32, (y as f32 + 0.5) / height as f32);
+                let d = distance(p);
+                let expected = (0.5 + d * spread).clamp(0.0, 1.0);
+                let value = sdf.buffer[(x + y * width) as usize];
+                match d > 0.0 && overlap {
+                    true => assert!(value > 0.5 && value < expected + tolerance, "{} at {:?}: {:?} > {:?}", name, p, value, expected),
+                    false => assert!((value - expected).abs() < tolerance, "{} at {:?}: {:?} != {:?}", name, p, value, expected),
+                }
+            }
+        }
+    }
+
+    /// Closed polygon going through `points`
+    fn polygon_lines(points: &[Vec2]) -> Vec<Line> {
+        (0..points.len()).map(|i| Line::Line { start: points[i], end: points[(i + 1) % points.len()] }).collect()
+    }
+
+    #[test]
+    fn test_shapes() {
+        let (size, spread) = (64, 4.0);
+        let check = |name: &str, shape: &Shape, tolerance: f32, distance: &dyn Fn(Vec2) -> f32| {
+            assert!(shape.contours().all(|contour| contour.is_closed() && contour.signed_area() > 0.0), "{}", name);
+            assert_eq!(validate_lines(shape), Vec::new(), "{}", name);
+            check_sdf(name, &sdf_generate(size, size, 0, spread, shape), spread, tolerance, false, distance);
+        };
+
+        let center = vec2(0.5, 0.5);
+        check("circle", &shapes::circle(center, 0.3), 1e-3, &|p| 0.3 - (p - center).length());
+        check("ellipse", &shapes::ellipse(center, vec2(0.3, 0.3)), 1e-3, &|p| 0.3 - (p - center).length());
+
+        let (min, max) = (vec2(0.2, 0.3), vec2(0.9, 0.7));
+        let rounded_box = |r: f32| move |p: Vec2| {
+            let half = (max - min) * 0.5;
+            let q = (p - (min + half)).abs() - half + vec2(r, r);
+            let outside = vec2(q[0].max(0.0), q[1].max(0.0)).length();
+            r - (outside + q[0].max(q[1]).min(0.0))
+        };
+
+        check("rectangle", &shapes::rectangle(min, max), 1e-5, &rounded_box(0.0));
+        check("rounded rectangle", &shapes::rounded_rectangle(min, max, 0.1), 1e-3, &rounded_box(0.1));
+        check("pill", &shapes::rounded_rectangle(min, max, 1.0), 1e-3, &rounded_box(0.2));
+
+        let (a, b) = (vec2(0.3, 0.6), vec2(0.7, 0.3));
+        check("capsule", &shapes::capsule(a, b, 0.15), 1e-3, &|p| {
+            let (pa, ba) = (p - a, b - a);
+            let h = (pa.dot(ba) / ba.dot(ba)).clamp(0.0, 1.0);
+            0.15 - (pa - ba * h).length()
+        });
+
+        // Polygons are compared with the same polygons written by hand
+        let hexagon: Vec<Vec2> = (0..6).map(|i| {
+            let angle = 0.3 + std::f32::consts::TAU * i as f32 / 6.0;
+            center + vec2(angle.cos(), angle.sin()) * 0.4
+        }).collect();
+        let hexagon = polygon_lines(&hexagon);
+        check("hexagon", &shapes::regular_polygon(center, 0.4, 6, 0.3), 1e-5, &|p| signed_distance(p, &hexagon));
+
+        let star: Vec<Vec2> = (0..10).map(|i| {
+            let angle = -std::f32::consts::FRAC_PI_2 + std::f32::consts::PI * i as f32 / 5.0;
+            center + vec2(angle.cos(), angle.sin()) * if i % 2 == 0 { 0.45 } else { 0.2 }
+        }).collect();
+        let star = polygon_lines(&star);
+        check("star", &shapes::star(center, 0.45, 0.2, 5, -std::f32::consts::FRAC_PI_2), 1e-5, &|p| signed_distance(p, &star));
+
+        let arrow = polygon_lines(&[
+            vec2(0.1, 0.45), vec2(0.6, 0.45), vec2(0.6, 0.3), vec2(0.9, 0.5),
+            vec2(0.6, 0.7), vec2(0.6, 0.55), vec2(0.1, 0.55),
+        ]);
+        check("arrow", &shapes::arrow(vec2(0.1, 0.5), vec2(0.9, 0.5), 0.1, 0.4, 0.3), 1e-5, &|p| signed_distance(p, &arrow));
+
+        // Ellipse points are on the ellipse
+        let ellipse = shapes::ellipse(center, vec2(0.4, 0.2));
+        for line in ellipse.iter() {
+            for i in 0..=10 {
+                let p = (line.point(i as f32 / 10.0) - center) / vec2(0.4, 0.2);
+                assert!((p.length() - 1.0).abs() < 3e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stroke() {
+        // Unless `exact_inside` is set, the distances inside the stroke can be lower than the true distance where the lines overlap
+        let (size, spread) = (64, 4.0);
+        let check = |name: &str, lines: &[Line], stroke: &Stroke, exact_inside: bool, distance: &dyn Fn(Vec2) -> f32| {
+            check_sdf(name, &sdf_stroke(size, size, 0, spread, lines, stroke), spread, 1e-5, !exact_inside, distance);
+        };
+
+        let rectangle = |min: Vec2, max: Vec2| move |p: Vec2| {
+            let half = (max - min) * 0.5;
+            let q = (p - (min + half)).abs() - half;
+            -(vec2(q[0].max(0.0), q[1].max(0.0)).length() + q[0].max(q[1]).min(0.0))
+        };
+
+        let polyline = [
+            Line::Line { start: vec2(0.1, 0.8), end: vec2(0.4, 0.3) },
+            Line::Quad { start: vec2(0.4, 0.3), end: vec2(0.6, 0.6), control: vec2(0.6, 0.2) },
+            Line::Curve { start: vec2(0.6, 0.6), end: vec2(0.9, 0.2), first_control: vec2(0.6, 0.9), second_control: vec2(0.9, 0.5) },
+        ];
+
+        // Round joins and caps are the distance to the lines minus half the width
+        let round = Stroke { join: LineJoin::Round, cap: LineCap::Round, ..Stroke::new(0.06) };
+        check("round", &polyline, &round, true, &|p| {
+            0.03 - polyline.iter().map(|line| line.distance(p[0], p[1])).fold(f32::MAX, f32::min)
+        });
+
+        // Caps
+        let line = [Line::Line { start: vec2(0.2, 0.5), end: vec2(0.8, 0.5) }];
+        check("butt", &line, &Stroke::new(0.1), true, &rectangle(vec2(0.2, 0.45), vec2(0.8, 0.55)));
+        let square = Stroke { cap: LineCap::Square, ..Stroke::new(0.1) };
+        check("square", &line, &square, true, &rectangle(vec2(0.15, 0.45), vec2(0.85, 0.55)));
+
+        // Joins of a right angle
+        let corner = [
+            Line::Line { start: vec2(0.2, 0.3), end: vec2(0.7, 0.3) },
+            Line::Line { start: vec2(0.7, 0.3), end: vec2(0.7, 0.8) },
+        ];
+
+        // The outlines of the miter and bevel joins are compared with the same outlines written by hand
+        let miter = polygon_lines(&[vec2(0.2, 0.25), vec2(0.75, 0.25), vec2(0.75, 0.8), vec2(0.65, 0.8), vec2(0.65, 0.35), vec2(0.2, 0.35)]);
+        check("miter", &corner, &Stroke::new(0.1), false, &|p| signed_distance(p, &miter));
+
+        // The miter limit of a right angle is sqrt(2)
+        let bevel = polygon_lines(&[vec2(0.2, 0.25), vec2(0.7, 0.25), vec2(0.75, 0.3), vec2(0.75, 0.8), vec2(0.65, 0.8), vec2(0.65, 0.35), vec2(0.2, 0.35)]);
+        check("bevel", &corner, &Stroke { join: LineJoin::Bevel, ..Stroke::new(0.1) }, false, &|p| signed_distance(p, &bevel));
+        check("miter limit", &corner, &Stroke { join: LineJoin::Miter(1.4), ..Stroke::new(0.1) }, false, &|p| signed_distance(p, &bevel));
+
+        let round_join = |p: Vec2| {
+            let legs = rectangle(vec2(0.2, 0.25), vec2(0.7, 0.35))(p).max(rectangle(vec2(0.65, 0.3), vec2(0.75, 0.8))(p));
+            let outside = legs.max(0.05 - (p - vec2(0.7, 0.3)).length());
+            let inside = (0.05 - corner.iter().map(|line| line.distance(p[0], p[1])).fold(f32::MAX, f32::min)).min(p[0] - 0.2).min(0.8 - p[1]);
+            match outside > 0.0 {
+                true => inside,
+                false => outside,
+            }
+        };
+        check("round join", &corner, &Stroke { join: LineJoin::Round, ..Stroke::new(0.1) }, true, &round_join);
+
+        // Closed contours have joins instead of caps
+        let square_path = [
+            Line::Line { start: vec2(0.3, 0.3), end: vec2(0.7, 0.3) },
+            Line::Line { start: vec2(0.7, 0.3), end: vec2(0.7, 0.7) },
+            Line::Line { start: vec2(0.7, 0.7), end: vec2(0.3, 0.7) },
+            Line::Line { start: vec2(0.3, 0.7), end: vec2(0.3, 0.3) },
+        ];
+
+        let (outer, inner) = (rectangle(vec2(0.25, 0.25), vec2(0.75, 0.75)), rectangle(vec2(0.35, 0.35), vec2(0.65, 0.65)));
+        check("closed", &square_path, &Stroke::new(0.1), false, &|p| outer(p).min(-inner(p)));
+        let long_dash = Stroke { dashes: vec![2.0, 0.1], ..Stroke::new(0.1) };
+        check("long dash", &square_path, &long_dash, false, &|p| outer(p).min(-inner(p)));
+
+        // Dashes
+        let dashed = Stroke { dashes: vec![0.1, 0.05], dash_offset: 0.05, ..Stroke::new(0.1) };
+        check("dashes", &line, &dashed, true, &|p| {
+            let dashes = [(0.2, 0.25), (0.3, 0.4), (0.45, 0.55), (0.6, 0.7), (0.75, 0.8)];
+            dashes.iter().map(|&(x0, x1)| rectangle(vec2(x0, 0.45), vec2(x1, 0.55))(p)).fold(f32::MIN, f32::max)
+        });
+
+        // A dash going through the first point of a closed contour has a join there. Each dash is an L around a corner of the square
+        let closed_dashes = Stroke { dashes: vec![0.2, 0.2], dash_offset: 0.1, ..Stroke::new(0.1) };
+        let corners: Vec<Vec<Line>> = [vec2(0.3, 0.3), vec2(0.7, 0.3), vec2(0.7, 0.7), vec2(0.3, 0.7)].iter().map(|&corner| {
+            let outward = vec2((corner[0] - 0.5).signum(), (corner[1] - 0.5).signum());
+            let outline = [vec2(0.05, 0.05), vec2(-0.1, 0.05), vec2(-0.1, -0.05), vec2(-0.05, -0.05), vec2(-0.05, -0.1), vec2(0.05, -0.1)];
+            polygon_lines(&outline.map(|offset| corner + outward * offset))
+        }).collect();
+        check("closed dashes", &square_path, &closed_dashes, false, &|p| {
+            corners.iter().map(|corner| signed_distance(p, corner)).fold(f32::MIN, f32::max)
+        });
+
+        let dotted = Stroke { cap: LineCap::Round, dashes: vec![0.0, 0.2], ..Stroke::new(0.1) };
+        check("dots", &line, &dotted, true, &|p| {
+            [0.2, 0.4, 0.6, 0.8].iter().map(|&x| 0.05 - (p - vec2(x, 0.5)).length()).fold(f32::MIN, f32::max)
+        });
+
+        // Dashes along curves keep the length of the pattern
+        let circle = [Line::Arc { center: vec2(0.5, 0.5), radius: vec2(0.3, 0.3), rotation: 0.0, start_angle: 0.0, sweep_angle: std::f32::consts::TAU }];
+        let dashed = Stroke { cap: LineCap::Round, dashes: vec![0.0, std::f32::consts::TAU * 0.3 / 12.0], ..Stroke::new(0.04) };
+        check("dotted circle", &circle, &dashed, true, &|p| {
+            (0..12).map(|i| {
+                let angle = std::f32::consts::TAU * i as f32 / 12.0;
+                0.02 - (p - vec2(0.5 + angle.cos() * 0.3, 0.5 + angle.sin() * 0.3)).length()
+            }).fold(f32::MIN, f32::max)
+        });
+    }
 
 }
@@ -1,11 +1,38 @@
 use crate::math::{Point, Vec2, vec2, vec3};
 use crate::mix;
+use crate::validate::straight_line;
 
-#[derive(Copy, Clone, Debug)]
+/// Maximum number of parts a curve is split into by [Line::flatten] and [Line::to_quads]
+const MAX_SEGMENTS: usize = 1024;
+
+/// Result of a closest point query on a line. See [Line::closest_point]
+///
+/// Distances are signed by the side of the line the query point is on: they are positive when the point is on the left of the line
+/// (`tangent.cross(p - point) >= 0.0`). As `y` points down, this is the inside of a contour with a positive [signed area](Line::signed_area).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ClosestPoint {
+    /// Parameter of the nearest point on the line, between `0.0` and `1.0`
+    pub t: f32,
+    /// Nearest point on the line
+    pub point: Point,
+    /// Unit tangent of the line at the nearest point, in the direction of the line.
+    /// Zero if all the points of the line are the same, and then the distances are not signed.
+    pub tangent: Vec2,
+    /// Signed distance between the query point and the nearest point
+    pub distance: f32,
+    /// Signed distance to the line extended past its end points along its tangents. Equal to `distance`, unless the nearest point is
+    /// an end point and the query point is past it, where this is the distance to the tangent at the end point.
+    pub pseudo_distance: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Line {
     Line { start: Point, end: Point },
     Quad { start: Point, end: Point, control: Point },
-    Curve { start: Point, end: Point, first_control: Point, second_control: Point }
+    Curve { start: Point, end: Point, first_control: Point, second_control: Point },
+    /// An elliptical arc. The point at the angle `a` is `center + rotate(rotation, [radius[0] * cos(a), radius[1] * sin(a)])`.
+    /// The arc goes from `start_angle` to `start_angle + sweep_angle`. Angles are in radians, and `sweep_angle` must be between `-2π` and `2π`.
+    Arc { center: Point, radius: Vec2, rotation: f32, start_angle: f32, sweep_angle: f32 },
 }
 
 impl Line {
@@ -17,103 +44,321 @@ impl Line {
             Self::Line { start, end } => {
                 let pa = p - start;
                 let ba = end - start;
-                let h = (pa.dot(ba) / ba.dot(ba)).clamp(0.0, 1.0);
+                let h = segment_parameter(pa, ba);
                 (pa - (ba*h)).length().abs()
             },
             Self::Quad { start, end, control } => {
-                // Credits to https://www.shadertoy.com/view/MlKcDD
-                let pa = control - start;
-                let pb = start - control * 2.0 + end;
-                let pc = pa * 2.0;
-                let pd = start - p;
-
-                let kk = 1.0/pb.dot(pb);
-                let kx = kk * pa.dot(pb);
-                let ky = kk * (2.0*pa.dot(pa)+pd.dot(pb)) / 3.0;
-                let kz = kk * pd.dot(pa);
-
-                let res;
-                
-                let p  = ky - kx*kx;
-                let q  = kx * (2.0*kx*kx - 3.0*ky) + kz;
-                let p3 = p*p*p;
-                let q2 = q*q;
-                let h  = q2 + (4.0*p3);
-
-                if h >= 0.0 {
-                    let h = h.sqrt();
-                    let x = (vec2(h, -h) - q) / 2.0;
-                    let uv = x.sign() * x.abs().powf(vec2(1.0/3.0, 1.0/3.0));
-                    let t = (uv[0]+uv[1]-kx).clamp(0.0, 1.0);
-                    let q = pd + (pc+pb*t)*t;
-                    res = q.dot(q);
+                let (_, distance) = closest_quad(p, start, end, control);
+                distance.sqrt().abs()
+            },
+            Self::Curve { start, end, first_control, second_control } => {
+                let (_, distance) = closest_curve(p, start, end, first_control, second_control);
+                distance.sqrt().abs()
+            },
+            Self::Arc { .. } => {
+                let (_, distance) = closest_arc(p, self);
+                distance.sqrt().abs()
+            }
+        }
+    }
+
+    /// Return the first point of the line
+    pub fn start(&self) -> Point {
+        match *self {
+            Self::Line { start, .. } | Self::Quad { start, .. } | Self::Curve { start, .. } => start,
+            Self::Arc { start_angle, .. } => self.arc_point(start_angle)
+        }
+    }
+
+    /// Return the last point of the line
+    pub fn end(&self) -> Point {
+        match *self {
+            Self::Line { end, .. } | Self::Quad { end, .. } | Self::Curve { end, .. } => end,
+            Self::Arc { start_angle, sweep_angle, .. } => self.arc_point(start_angle + sweep_angle)
+        }
+    }
+
+    /// Return the point on the line at `t`, where `t` goes from 0.0 (start) to 1.0 (end)
+    pub(crate) fn point(&self, t: f32) -> Point {
+        match *self {
+            Self::Line { start, end } => start + (end - start) * t,
+            Self::Quad { start, end, control } => {
+                let mt = 1.0 - t;
+                start * (mt * mt) + control * (2.0 * mt * t) + end * (t * t)
+            },
+            Self::Curve { start, end, first_control, second_control } => compute_curve(t, start, end, first_control, second_control),
+            Self::Arc { start_angle, sweep_angle, .. } => self.arc_point(start_angle + sweep_angle * t)
+        }
+    }
+
+    /// Return the point of an arc at the angle `angle`. Returns `start` for the other lines.
+    fn arc_point(&self, angle: f32) -> Point {
+        let (x, y) = self.arc_point_f64(angle as f64);
+        vec2(x as f32, y as f32)
+    }
+
+    /// Same as [Line::arc_point], but evaluated in double precision
+    fn arc_point_f64(&self, angle: f64) -> (f64, f64) {
+        match *self {
+            Self::Arc { center, radius, rotation, .. } => {
+                let (sin, cos) = (rotation as f64).sin_cos();
+                let (angle_sin, angle_cos) = quarter_sin_cos(angle);
+                let x = radius[0] as f64 * angle_cos;
+                let y = radius[1] as f64 * angle_sin;
+                (center[0] as f64 + (cos * x) - (sin * y), center[1] as f64 + (sin * x) + (cos * y))
+            },
+            _ => {
+                let start = self.start();
+                (start[0] as f64, start[1] as f64)
+            }
+        }
+    }
+
+    /// Return the (non normalized) direction of the line at `t`.
+    /// If the derivative vanishes at `t` (ex: a control point on top of an end point), the direction toward the next control point is used instead
+    pub(crate) fn direction(&self, t: f32) -> Vec2 {
+        match *self {
+            Self::Line { start, end } => end - start,
+            Self::Quad { start, end, control } => {
+                let d = (control - start) * (2.0 * (1.0 - t)) + (end - control) * (2.0 * t);
+                match d == vec2(0.0, 0.0) {
+                    true => end - start,
+                    false => d
+                }
+            },
+            Self::Curve { start, end, first_control, second_control } => {
+                let mt = 1.0 - t;
+                let d = (first_control - start) * (3.0 * mt * mt)
+                    + (second_control - first_control) * (6.0 * mt * t)
+                    + (end - second_control) * (3.0 * t * t);
+
+                if d != vec2(0.0, 0.0) {
+                    d
+                } else if t < 0.5 {
+                    second_control - start
                 } else {
-                    let z = (-p).sqrt();
-                    let v = (q / (p*z*2.0)).acos() / 3.0;
-                    let m = v.cos();
-                    let n = v.sin() * 1.732050808;
-                    let t = (vec3(m+m, -n-m, n-m)*z-kx).clamp(0.0, 1.0);
-                    let qx = pd + (pc+pb*t[0]) * t[0];
-                    let dx = qx.dot(qx);
-                    let qy = pd + (pc+pb*t[1]) * t[1];
-                    let dy = qy.dot(qy);
-                    res = dx.min(dy);
+                    end - first_control
                 }
-                
-                res.sqrt().abs()
+            },
+            Self::Arc { radius, rotation, start_angle, sweep_angle, .. } => {
+                let angle = start_angle + sweep_angle * t;
+                let (sin, cos) = rotation.sin_cos();
+                let x = -radius[0] * angle.sin() * sweep_angle;
+                let y = radius[1] * angle.cos() * sweep_angle;
+                let d = vec2((cos * x) - (sin * y), (sin * x) + (cos * y));
+                match d == vec2(0.0, 0.0) {
+                    true => self.end() - self.start(),
+                    false => d
+                }
+            }
+        }
+    }
+
+    /// Return the parameter `t` of the point on the line nearest to `p`, and the distance between them.
+    /// Uses the same solvers as [Line::distance]
+    pub(crate) fn closest(&self, p: Vec2) -> (f32, f32) {
+        match *self {
+            Self::Line { start, end } => {
+                let pa = p - start;
+                let ba = end - start;
+                let h = segment_parameter(pa, ba);
+                (h, (pa - (ba*h)).length().abs())
+            },
+            Self::Quad { start, end, control } => {
+                let (t, distance) = closest_quad(p, start, end, control);
+                (t, distance.sqrt().abs())
             },
             Self::Curve { start, end, first_control, second_control } => {
-                const STEPS: usize = 30;
-                let solve_distance = |i, t, min_distance: &mut f32, closest_step: &mut usize| {
-                    let curve_pt = compute_curve(t, start, end, first_control, second_control);
+                let (t, distance) = closest_curve(p, start, end, first_control, second_control);
+                (t, distance.sqrt().abs())
+            },
+            Self::Arc { .. } => {
+                let (t, distance) = closest_arc(p, self);
+                (t, distance.sqrt().abs())
+            }
+        }
+    }
 
-                    let x = p[0]-curve_pt[0];
-                    let y = p[1]-curve_pt[1];
-                    let distance = x*x + y*y;   // No need to square the distance everytime, we do it once at the end
+    /// Return the point of the line nearest to `p`, with its parameter, the tangent of the line there, and the side of the line `p` is on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use easy_signed_distance_field as sdf;
+    ///
+    /// let line = sdf::Line::Line { start: sdf::vec2(0.0, 0.0), end: sdf::vec2(1.0, 0.0) };
+    /// let closest = line.closest_point(sdf::vec2(2.0, 0.5));
+    /// assert_eq!((closest.t, closest.point), (1.0, sdf::vec2(1.0, 0.0)));
+    /// assert_eq!(closest.pseudo_distance, 0.5);
+    /// ```
+    pub fn closest_point(&self, p: Point) -> ClosestPoint {
+        let (t, distance) = self.closest(p);
+        let point = self.point(t);
+        let tangent = match self.direction(t) {
+            direction if direction == vec2(0.0, 0.0) => direction,
+            direction => direction.normalize(),
+        };
 
-                    if distance < *min_distance {
-                        *min_distance = distance;
-                        *closest_step = i;
-                    }
-                };
-                
-                // Brute force method, because a closed-form solution would be too complex
-                // see for yourself: https://www.shadertoy.com/view/4sKyzW
-                let mut min_distance = f32::MAX;
-                let mut closest_step = 0;
-                
-                // Step 1: Coarse check
-                let coarse_step_value = 1.0 / STEPS as f32;
-                for i in 0..=STEPS {
-                    let t = coarse_step_value * (i as f32);
-                    solve_distance(i, t, &mut min_distance, &mut closest_step);
-                }
+        let offset = p - point;
+        let distance = match tangent.cross(offset) < 0.0 {
+            true => -distance,
+            false => distance,
+        };
 
-                // Step 2: fine check
-                let bounds_min = match closest_step == 0 {
-                    true => 0.0,
-                    false => (closest_step - 1) as f32 * coarse_step_value,
-                };
+        // Past an end point, the line is extended along its tangent
+        let past_end = (t <= 0.0 && offset.dot(tangent) < 0.0) || (t >= 1.0 && offset.dot(tangent) > 0.0);
+        let pseudo_distance = match past_end {
+            true => tangent.cross(offset),
+            false => distance,
+        };
 
-                let bounds_max = match closest_step == STEPS {
-                    true => 1.0,
-                    false => (closest_step + 1) as f32 * coarse_step_value,
-                };
+        ClosestPoint { t, point, tangent, distance, pseudo_distance }
+    }
 
-                let fine_step = (bounds_max - bounds_min) / STEPS as f32;
-                for i in 0..=STEPS {
-                    let t = bounds_min + (i as f32 * fine_step);
-                    solve_distance(i, t, &mut min_distance, &mut closest_step)
-                }
+    /// Return the exact bounding box (min, max) of the line. The extrema of the curves are solved, so the box
+    /// can be smaller than the box of the control points, and the curve always touches each side of the box.
+    pub fn bounds(&self) -> (Point, Point) {
+        let (start, end) = (self.start(), self.end());
+        let mut min = vec2(start[0].min(end[0]), start[1].min(end[1]));
+        let mut max = vec2(start[0].max(end[0]), start[1].max(end[1]));
+        let mut extrema = [0.0; 2];
+        for axis in 0..2 {
+            let count = self.extrema(axis, &mut extrema);
+            for &t in extrema[..count].iter() {
+                let (x, y) = self.point_f64(t);
+                let value = [x, y][axis] as f32;
+                min[axis] = min[axis].min(value);
+                max[axis] = max[axis].max(value);
+            }
+        }
 
-                min_distance.sqrt().abs()
+        (min, max)
+    }
+
+    /// Split the line in two at the parameter `t` (between `0.0` and `1.0`), using the de Casteljau algorithm.
+    /// The first line goes from the start to `point(t)` and the second line from `point(t)` to the end. Arcs are split at the angle of `t`.
+    pub fn split(&self, t: f32) -> (Line, Line) {
+        let lerp = |a: Vec2, b: Vec2| a + (b - a) * t;
+        match *self {
+            Self::Line { start, end } => {
+                let mid = lerp(start, end);
+                (Self::Line { start, end: mid }, Self::Line { start: mid, end })
+            },
+            Self::Quad { start, end, control } => {
+                let c0 = lerp(start, control);
+                let c1 = lerp(control, end);
+                let mid = lerp(c0, c1);
+                (Self::Quad { start, end: mid, control: c0 }, Self::Quad { start: mid, end, control: c1 })
+            },
+            Self::Curve { start, end, first_control, second_control } => {
+                let a = lerp(start, first_control);
+                let b = lerp(first_control, second_control);
+                let c = lerp(second_control, end);
+                let ab = lerp(a, b);
+                let bc = lerp(b, c);
+                let mid = lerp(ab, bc);
+                (
+                    Self::Curve { start, end: mid, first_control: a, second_control: ab },
+                    Self::Curve { start: mid, end, first_control: bc, second_control: c }
+                )
+            },
+            Self::Arc { center, radius, rotation, start_angle, sweep_angle } => {
+                let sweep = sweep_angle * t;
+                (
+                    Self::Arc { center, radius, rotation, start_angle, sweep_angle: sweep },
+                    Self::Arc { center, radius, rotation, start_angle: start_angle + sweep, sweep_angle: sweep_angle - sweep }
+                )
             }
         }
     }
 
+    /// Append the points of a polyline that follows the line to `points`. The start of the line is not added, so that
+    /// consecutive lines can be flattened in the same vector. Every point of the line is at most `tolerance` away from the polyline.
+    ///
+    /// Curves are split in equal parts of the parameter. The number of parts is capped at 1024 per line, so the tolerance is not
+    /// reached for a `tolerance` too small for the size of the curve (ex: `0.0`), and the polyline is only as close as 1024 parts allow.
+    /// Straight lines only add their end point.
+    pub fn flatten(&self, tolerance: f32, points: &mut Vec<Point>) {
+        // The distance between a curve and its chord is at most `max(|B''|) * h^2 / 8`, where `h` is the parameter length of the chord
+        let second_derivative = match *self {
+            Self::Line { .. } => 0.0,
+            Self::Quad { start, end, control } => (start - control * 2.0 + end).length() * 2.0,
+            Self::Curve { start, end, first_control, second_control } => {
+                let a = (start - first_control * 2.0 + second_control).length();
+                let b = (first_control - second_control * 2.0 + end).length();
+                a.max(b) * 6.0
+            },
+            Self::Arc { radius, sweep_angle, .. } => radius[0].max(radius[1]) * sweep_angle * sweep_angle,
+        };
+
+        let parts = segment_count((second_derivative / (8.0 * tolerance)).sqrt());
+        for i in 1..parts {
+            points.push(self.point(i as f32 / parts as f32));
+        }
+
+        points.push(self.end());
+    }
+
+    /// Approximate a cubic curve with quadratic curves, at most `tolerance` away from the cubic curve. Other lines are returned as is.
+    ///
+    /// The cubic curve is split in equal parts of the parameter, and each part is replaced by a single quadratic curve.
+    /// As with [Line::flatten], the number of parts is capped at 1024 and the tolerance is not reached past this cap.
+    pub fn to_quads(&self, tolerance: f32) -> Vec<Line> {
+        let Self::Curve { start, end, first_control, second_control } = *self else {
+            return vec![*self];
+        };
+
+        // A cubic and the quadratic with the control `(3*(c1 + c2) - (p0 + p3)) / 4` are at most `sqrt(3)/36 * |p3 - 3*c2 + 3*c1 - p0|` apart.
+        // Splitting the cubic in `n` parts divides this third difference by `n^3`
+        let third_difference = (end - second_control * 3.0 + first_control * 3.0 - start).length();
+        let parts = segment_count((3f32.sqrt() / 36.0 * third_difference / tolerance).cbrt());
+
+        let mut quads = Vec::with_capacity(parts);
+        let mut rest = *self;
+        for i in 0..parts {
+            let (part, next) = match i + 1 < parts {
+                true => rest.split(1.0 / (parts - i) as f32),
+                false => (rest, rest),
+            };
+
+            let Self::Curve { start, end, first_control, second_control } = part else { unreachable!() };
+            let control = ((first_control + second_control) * 3.0 - start - end) / 4.0;
+            quads.push(Self::Quad { start, end, control });
+            rest = next;
+        }
+
+        quads
+    }
+
     /// Write up to 3 intersections in `out` at height `y`
     pub fn intersections(&self, y: f32, out: &mut [f32; 3]) -> usize {
         match *self {
+            Self::Arc { center, radius, rotation, start_angle, sweep_angle } => {
+                // Solve `a*cos(angle) + b*sin(angle) = y - center.y`, which is `r*cos(angle - phase) = y - center.y`
+                let (sin, cos) = (rotation as f64).sin_cos();
+                let a = radius[0] as f64 * sin;
+                let b = radius[1] as f64 * cos;
+                let r = (a*a + b*b).sqrt();
+                let h = (y - center[1]) as f64;
+                if r == 0.0 || h.abs() > r {
+                    return 0;
+                }
+
+                let phase = b.atan2(a);
+                let delta = (h / r).clamp(-1.0, 1.0).acos();
+
+                let mut count = 0;
+                for angle in [phase - delta, phase + delta] {
+                    if let Some(t) = arc_parameter(angle, start_angle as f64, sweep_angle as f64) {
+                        if count == 0 || delta != 0.0 {
+                            out[count] = self.arc_point_f64(start_angle as f64 + sweep_angle as f64 * t).0 as f32;
+                            count += 1;
+                        }
+                    }
+                }
+
+                count
+            },
             Self::Line { start, end } => {
                 if (y >= start[1] && y <= end[1]) || (y >= end[1] && y < start[1]) {
                     let h = (y-start[1])/(end[1]-start[1]);
@@ -318,6 +563,170 @@ impl Line {
         }
     }
 
+    /// Write up to 3 intersections in `out` at height `y`, and the direction of the line at each intersection in `directions`:
+    /// `1` if the line goes toward a higher `y` and `-1` if it goes toward a lower `y`.
+    /// 
+    /// Unlike [Line::intersections], the crossings are counted so that they can be used to decide if a point is inside a closed shape:
+    /// the line is split in parts going in a single direction, and each part includes its lowest point but not its highest point.
+    /// This way a vertex shared by two lines, or the extremum of a curve, is only counted when the shape actually crosses `y`.
+    pub fn intersections_with_direction(&self, y: f32, out: &mut [f32; 3], directions: &mut [i32; 3]) -> usize {
+        let y = y as f64;
+        let mut count = 0;
+        let mut crossing = |y0: f64, y1: f64, root: &dyn Fn() -> f32| {
+            if y0 != y1 && y >= y0.min(y1) && y < y0.max(y1) {
+                out[count] = root();
+                directions[count] = if y1 > y0 { 1 } else { -1 };
+                count += 1;
+            }
+        };
+
+        if let Self::Line { start, end } = *self {
+            let (y0, y1) = (start[1] as f64, end[1] as f64);
+            crossing(y0, y1, &|| mix(start[0], end[0], ((y - y0) / (y1 - y0)) as f32));
+            return count;
+        }
+
+        // Split the curve where its direction changes on the y axis
+        let mut splits = [0.0, 1.0, 1.0, 1.0];
+        let extrema = self.extrema(1, &mut splits[1..3]);
+        let parts = extrema + 1;
+
+        for i in 0..parts {
+            let (t0, t1) = (splits[i], splits[i+1]);
+            // The end points use the same rounding as `start` and `end`, so that a vertex shared with the next line has the same height in both lines
+            let y_at = |t: f64| match t {
+                0.0 => self.start()[1] as f64,
+                1.0 => self.end()[1] as f64,
+                _ => self.point_f64(t).1
+            };
+            let (y0, y1) = (y_at(t0), y_at(t1));
+            crossing(y0, y1, &|| {
+                // The part is monotonic, so the crossing is found with a bisection
+                let (mut lo, mut hi) = (t0, t1);
+                for _ in 0..48 {
+                    let mid = (lo + hi) * 0.5;
+                    match (self.point_f64(mid).1 < y) == (y0 < y1) {
+                        true => { lo = mid; },
+                        false => { hi = mid; }
+                    }
+                }
+
+                self.point_f64((lo + hi) * 0.5).0 as f32
+            });
+        }
+
+        count
+    }
+
+    /// Write the parameters of the extrema of the curve on the `axis` (0: x, 1: y) in `out`, in increasing order, and return their count.
+    /// Only the extrema strictly between the end points are returned.
+    fn extrema(&self, axis: usize, out: &mut [f64]) -> usize {
+        let mut count = 0;
+        let mut push = |t: f64| {
+            if t > 0.0 && t < 1.0 && (count == 0 || out[count-1] != t) {
+                out[count] = t;
+                count += 1;
+            }
+        };
+
+        match *self {
+            Self::Line { .. } => {},
+            Self::Arc { .. } => {
+                count = self.arc_extrema(axis, out);
+            },
+            Self::Quad { start, end, control } => {
+                let (a, b, c) = (start[axis] as f64, control[axis] as f64, end[axis] as f64);
+                let d = a - 2.0 * b + c;
+                if d != 0.0 {
+                    push((a - b) / d);
+                }
+            },
+            Self::Curve { start, end, first_control, second_control } => {
+                let (p0, p1, p2, p3) = (start[axis] as f64, first_control[axis] as f64, second_control[axis] as f64, end[axis] as f64);
+
+                // Roots of the derivative: a*t^2 + b*t + c
+                let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+                let b = 2.0 * (p0 - 2.0 * p1 + p2);
+                let c = p1 - p0;
+                if a == 0.0 {
+                    if b != 0.0 {
+                        push(-c / b);
+                    }
+                } else {
+                    let discriminant = b * b - 4.0 * a * c;
+                    if discriminant >= 0.0 {
+                        let q = discriminant.sqrt();
+                        let (r0, r1) = ((-b - q) / (2.0 * a), (-b + q) / (2.0 * a));
+                        push(r0.min(r1));
+                        push(r0.max(r1));
+                    }
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Same as [Line::point], but evaluated in double precision
+    fn point_f64(&self, t: f64) -> (f64, f64) {
+        let mt = 1.0 - t;
+        let eval = |v: &[f32]| -> f64 {
+            match *v {
+                [a, b] => a as f64 * mt + b as f64 * t,
+                [a, b, c] => (a as f64 * mt * mt) + (b as f64 * 2.0 * mt * t) + (c as f64 * t * t),
+                [a, b, c, d] => (a as f64 * mt * mt * mt) + (b as f64 * 3.0 * mt * mt * t) + (c as f64 * 3.0 * mt * t * t) + (d as f64 * t * t * t),
+                _ => 0.0
+            }
+        };
+
+        match *self {
+            Self::Line { start, end } => (eval(&[start[0], end[0]]), eval(&[start[1], end[1]])),
+            Self::Quad { start, end, control } => (eval(&[start[0], control[0], end[0]]), eval(&[start[1], control[1], end[1]])),
+            Self::Curve { start, end, first_control, second_control } => (
+                eval(&[start[0], first_control[0], second_control[0], end[0]]),
+                eval(&[start[1], first_control[1], second_control[1], end[1]]),
+            ),
+            Self::Arc { start_angle, sweep_angle, .. } => self.arc_point_f64(start_angle as f64 + sweep_angle as f64 * t)
+        }
+    }
+
+    /// Write the parameters of the extrema of an arc on the `axis` (0: x, 1: y) in `out`, in increasing order, and return their count.
+    /// Only the extrema strictly between the end points are returned.
+    fn arc_extrema(&self, axis: usize, out: &mut [f64]) -> usize {
+        let Self::Arc { radius, rotation, start_angle, sweep_angle, .. } = *self else {
+            return 0;
+        };
+
+        // The derivative of `a*cos(angle) + b*sin(angle)` is zero when `tan(angle) = b / a`
+        let (sin, cos) = (rotation as f64).sin_cos();
+        let (a, b) = match axis {
+            0 => (radius[0] as f64 * cos, -radius[1] as f64 * sin),
+            _ => (radius[0] as f64 * sin, radius[1] as f64 * cos),
+        };
+
+        if a == 0.0 && b == 0.0 {
+            return 0;
+        }
+
+        let mut parameters = [0.0; 2];
+        let mut count = 0;
+        let phase = b.atan2(a);
+        for angle in [phase, phase + std::f64::consts::PI] {
+            match arc_parameter(angle, start_angle as f64, sweep_angle as f64) {
+                Some(t) if t > 0.0 && t < 1.0 => {
+                    parameters[count] = t;
+                    count += 1;
+                },
+                _ => {}
+            }
+        }
+
+        parameters[..count].sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let count = count.min(out.len());
+        out[..count].copy_from_slice(&parameters[..count]);
+        count
+    }
+
     /// Normalize the curve in place using the values provided. Assumes the curves coordinates are in a `0..width` and `0..height` range
     /// See also [Line::normalize_to]
     pub fn normalize(&mut self, width: f32, height: f32) {
@@ -336,7 +745,8 @@ impl Line {
         match *self {
             Self::Line { start, end } => Self::Line { start: start / p, end: end / p },
             Self::Quad { start, end, control } => Self::Quad { start: start / p, end: end / p, control: control / p },
-            Self::Curve { start, end, first_control, second_control } => Self::Curve { start: start / p, end: end / p, first_control: first_control / p,  second_control: second_control / p }
+            Self::Curve { start, end, first_control, second_control } => Self::Curve { start: start / p, end: end / p, first_control: first_control / p,  second_control: second_control / p },
+            Self::Arc { .. } => self.normalize_to_with_offset(0.0, 0.0, width, height)
         }
     }
 
@@ -356,6 +766,73 @@ impl Line {
                 end: (end-o) / p,
                 first_control: (first_control - o) / p,
                 second_control: (second_control - o) / p 
+            },
+            Self::Arc { center, radius, rotation, start_angle, sweep_angle } => {
+                let (radius, rotation, start_angle, sweep_angle) = transform_arc(radius, rotation, start_angle, sweep_angle, [1.0 / width as f64, 0.0, 0.0, 1.0 / height as f64]);
+                Self::Arc { center: (center-o) / p, radius, rotation, start_angle, sweep_angle }
+            }
+        }
+    }
+
+    /// Apply the affine transform `matrix` (`[a, b, c, d, e, f]`, as in SVG) to the line: `x' = a*x + c*y + e` and `y' = b*x + d*y + f`
+    #[cfg(feature="svg")]
+    pub(crate) fn transform(&self, matrix: [f32; 6]) -> Self {
+        let [a, b, c, d, e, f] = matrix;
+        let p = |p: Point| vec2(a*p[0] + c*p[1] + e, b*p[0] + d*p[1] + f);
+        match *self {
+            Self::Line { start, end } => Self::Line { start: p(start), end: p(end) },
+            Self::Quad { start, end, control } => Self::Quad { start: p(start), end: p(end), control: p(control) },
+            Self::Curve { start, end, first_control, second_control } => Self::Curve {
+                start: p(start),
+                end: p(end),
+                first_control: p(first_control),
+                second_control: p(second_control)
+            },
+            Self::Arc { center, radius, rotation, start_angle, sweep_angle } => {
+                let linear = [a as f64, c as f64, b as f64, d as f64];
+                let (radius, rotation, start_angle, sweep_angle) = transform_arc(radius, rotation, start_angle, sweep_angle, linear);
+                Self::Arc { center: p(center), radius, rotation, start_angle, sweep_angle }
+            }
+        }
+    }
+
+    /// Reverse the direction of the line in place: the start becomes the end, and the order of the control points is swapped
+    pub fn reverse(&mut self) {
+        *self = match *self {
+            Self::Line { start, end } => Self::Line { start: end, end: start },
+            Self::Quad { start, end, control } => Self::Quad { start: end, end: start, control },
+            Self::Curve { start, end, first_control, second_control } => Self::Curve {
+                start: end,
+                end: start,
+                first_control: second_control,
+                second_control: first_control
+            },
+            Self::Arc { center, radius, rotation, start_angle, sweep_angle } => Self::Arc {
+                center,
+                radius,
+                rotation,
+                start_angle: start_angle + sweep_angle,
+                sweep_angle: -sweep_angle
+            }
+        };
+    }
+
+    /// Return the signed area between the line and the origin, using the shoelace formula extended to curves.
+    /// The sum of the signed areas of the lines of a closed contour is the signed area of the contour:
+    /// positive if the contour goes clockwise when `y` points down (as in the normalized space of a raster).
+    pub fn signed_area(&self) -> f32 {
+        match *self {
+            Self::Line { start, end } => start.cross(end) / 2.0,
+            Self::Quad { start, end, control } => {
+                (2.0*start.cross(control) + start.cross(end) + 2.0*control.cross(end)) / 6.0
+            },
+            Self::Curve { start, end, first_control, second_control } => {
+                let (p0, p1, p2, p3) = (start, first_control, second_control, end);
+                (6.0*p0.cross(p1) + 3.0*p0.cross(p2) + p0.cross(p3) + 3.0*p1.cross(p2) + 3.0*p1.cross(p3) + 6.0*p2.cross(p3)) / 20.0
+            },
+            Self::Arc { center, radius, sweep_angle, .. } => {
+                // The triangle between the origin, the center and the end points, and the sector of the ellipse
+                (center.cross(self.end() - self.start()) + (radius[0] * radius[1] * sweep_angle)) / 2.0
             }
         }
     }
@@ -372,12 +849,29 @@ impl Line {
                 end: (end * p1) - p2,
                 first_control: (first_control * p1) - p2,
                 second_control: (second_control * p1) - p2
+            },
+            Self::Arc { center, radius, rotation, start_angle, sweep_angle } => Self::Arc {
+                center: (center * p1) - p2,
+                radius,
+                rotation: -rotation,
+                start_angle: -start_angle,
+                sweep_angle: -sweep_angle
             }
         };
     }
 
 }
 
+/// Return the parameter of the point nearest to `pa` on the segment `ba` (relative to the start of the segment).
+/// Zero length segments return 0.0 instead of NaN
+fn segment_parameter(pa: Vec2, ba: Vec2) -> f32 {
+    let length = ba.dot(ba);
+    match length > 0.0 {
+        true => (pa.dot(ba) / length).clamp(0.0, 1.0),
+        false => 0.0
+    }
+}
+
 fn compute_curve(t: f32, start: Vec2, end: Vec2, control1: Vec2, control2: Vec2) -> Vec2 {
     let t2 = t * t;
     let t3 = t2 * t;
@@ -391,6 +885,409 @@ fn compute_curve(t: f32, start: Vec2, end: Vec2, control1: Vec2, control2: Vec2)
     )
 }
 
+/// Return the parameter `t` of the point on the quadratic curve nearest to `p` and its squared distance from `p`
+fn closest_quad(p: Vec2, start: Vec2, end: Vec2, control: Vec2) -> (f32, f32) {
+    // Credits to https://www.shadertoy.com/view/MlKcDD
+    let pa = control - start;
+    let pb = start - control * 2.0 + end;
+
+    // The closed-form solution is unstable when the curve is almost straight (`pb` goes to zero).
+    // A straight curve is a line (the same conversion as `clean_lines`), and the cubic solver is used for the other ones.
+    let chord = end - start;
+    if pb.dot(pb) <= 1e-3 * chord.dot(chord) {
+        if let Some(Line::Line { start, end }) = straight_line(&Line::Quad { start, end, control }) {
+            let (pa, ba) = (p - start, end - start);
+            let h = segment_parameter(pa, ba);
+            let q = pa - (ba*h);
+            return (straight_quad_parameter(h, segment_parameter(control - start, ba)), q.dot(q));
+        }
+
+        let third = 2.0 / 3.0;
+        return closest_curve(p, start, end, start + (control - start) * third, end + (control - end) * third);
+    }
+    let pc = pa * 2.0;
+    let pd = start - p;
+
+    let kk = 1.0/pb.dot(pb);
+    let kx = kk * pa.dot(pb);
+    let ky = kk * (2.0*pa.dot(pa)+pd.dot(pb)) / 3.0;
+    let kz = kk * pd.dot(pa);
+
+    let p  = ky - kx*kx;
+    let q  = kx * (2.0*kx*kx - 3.0*ky) + kz;
+    let p3 = p*p*p;
+    let q2 = q*q;
+    let h  = q2 + (4.0*p3);
+
+    if h >= 0.0 {
+        let h = h.sqrt();
+        let x = (vec2(h, -h) - q) / 2.0;
+        let uv = x.sign() * x.abs().powf(vec2(1.0/3.0, 1.0/3.0));
+        let t = (uv[0]+uv[1]-kx).clamp(0.0, 1.0);
+        let q = pd + (pc+pb*t)*t;
+        (t, q.dot(q))
+    } else {
+        let z = (-p).sqrt();
+        let v = (q / (p*z*2.0)).acos() / 3.0;
+        let m = v.cos();
+        let n = v.sin() * 1.732050808;
+        let t = (vec3(m+m, -n-m, n-m)*z-kx).clamp(0.0, 1.0);
+        let qx = pd + (pc+pb*t[0]) * t[0];
+        let dx = qx.dot(qx);
+        let qy = pd + (pc+pb*t[1]) * t[1];
+        let dy = qy.dot(qy);
+        let res = dx.min(dy);
+        match res == dx {
+            true => (t[0], res),
+            false => (t[1], res)
+        }
+    }
+}
+
+/// Return the parameter `t` of a straight quadratic curve at the parameter `h` of its chord. `c` is the parameter of the control point on the chord.
+/// Solves `2*c*t*(1 - t) + t^2 = h`, which only has one root in [0, 1] as the curve goes in a single direction.
+fn straight_quad_parameter(h: f32, c: f32) -> f32 {
+    let denominator = c + (c*c + (1.0 - 2.0*c) * h).max(0.0).sqrt();
+    match denominator > 0.0 {
+        true => (h / denominator).clamp(0.0, 1.0),
+        false => h,
+    }
+}
+
+/// Return the parameter `t` of the point on the cubic curve nearest to `p` and its squared distance from `p`
+/// 
+/// The nearest point is either an end point, or a point where `(curve(t) - p) . curve'(t) = 0`. This quintic
+/// has no closed-form solution, so its roots in [0, 1] are isolated between the roots of its derivatives and then
+/// refined to a precision of 1e-12 (see [polynomial_roots]). Unlike sampling, this cannot miss the nearest point on loops or cusps.
+/// The only error left is the conversion of the result to f32.
+fn closest_curve(p: Vec2, start: Vec2, end: Vec2, first_control: Vec2, second_control: Vec2) -> (f32, f32) {
+    let point = |v: Vec2| [v[0] as f64, v[1] as f64];
+    let (p0, p1, p2, p3, p) = (point(start), point(first_control), point(second_control), point(end), point(p));
+
+    // Power basis of the curve, relative to `p`: a*t^3 + b*t^2 + c*t + d
+    let mut a = [0.0; 2];
+    let mut b = [0.0; 2];
+    let mut c = [0.0; 2];
+    let mut d = [0.0; 2];
+    for i in 0..2 {
+        a[i] = -p0[i] + 3.0*p1[i] - 3.0*p2[i] + p3[i];
+        b[i] = 3.0*p0[i] - 6.0*p1[i] + 3.0*p2[i];
+        c[i] = 3.0*(p1[i] - p0[i]);
+        d[i] = p0[i] - p[i];
+    }
+
+    let dot = |u: [f64; 2], v: [f64; 2]| u[0]*v[0] + u[1]*v[1];
+    let squared_distance = |t: f64| {
+        let x = ((a[0]*t + b[0])*t + c[0])*t + d[0];
+        let y = ((a[1]*t + b[1])*t + c[1])*t + d[1];
+        x*x + y*y
+    };
+
+    // (curve(t) - p) . curve'(t), lowest degree first
+    let quintic = [
+        dot(c, d),
+        dot(c, c) + 2.0*dot(b, d),
+        3.0*(dot(b, c) + dot(a, d)),
+        4.0*dot(a, c) + 2.0*dot(b, b),
+        5.0*dot(a, b),
+        3.0*dot(a, a),
+    ];
+
+    // The quintic is monotonic between the roots of its derivative. The distance has a local minimum
+    // in an interval only if the quintic goes from negative to positive.
+    let mut quartic = [0.0; 5];
+    for i in 0..5 {
+        quartic[i] = (i + 1) as f64 * quintic[i + 1];
+    }
+
+    let mut bounds = [0.0; 6];
+    let count = polynomial_roots(&quartic, (&mut bounds[..5]).try_into().unwrap());
+    bounds[count] = 1.0;
+
+    let evaluate = |t: f64| quintic.iter().rev().fold(0.0, |acc, &c| acc*t + c);
+
+    let mut closest_t = 0.0;
+    let mut min_distance = squared_distance(0.0);
+    let mut low = 0.0;
+    let mut f_low = evaluate(low);
+    for &high in bounds[..count + 1].iter() {
+        let f_high = evaluate(high);
+        let t = match f_low < 0.0 && f_high > 0.0 {
+            true => monotonic_root(&quintic, low, high, true),
+            false => high,
+        };
+
+        let distance = squared_distance(t);
+        if distance < min_distance {
+            min_distance = distance;
+            closest_t = t;
+        }
+
+        low = high;
+        f_low = f_high;
+    }
+
+    (closest_t as f32, min_distance as f32)
+}
+
+/// Find the roots in [0, 1] of the polynomial `coefficients[0] + coefficients[1]*t + coefficients[2]*t^2 + ...` (degree 5 at most).
+/// Return the number of roots written in `out`, in increasing order.
+/// 
+/// The roots of the derivative split [0, 1] into intervals where the polynomial is monotonic, so each interval
+/// holds at most one root, and only if the polynomial changes sign. Roots where the polynomial touches 0 without changing sign can be missed.
+fn polynomial_roots(coefficients: &[f64], out: &mut [f64; 5]) -> usize {
+    let degree = coefficients.len() - 1;
+    if degree == 0 {
+        return 0;
+    }
+
+    if degree == 1 && coefficients[1] != 0.0 {
+        let root = -coefficients[0] / coefficients[1];
+        out[0] = root;
+        return (0.0..=1.0).contains(&root) as usize;
+    }
+
+    if degree == 2 && coefficients[2] != 0.0 {
+        let [c, b, a] = [coefficients[0], coefficients[1], coefficients[2]];
+        let discriminant = b*b - 4.0*a*c;
+        if discriminant < 0.0 {
+            return 0;
+        }
+
+        // Avoid the cancellation between `b` and the square root
+        let q = -0.5 * (b + discriminant.sqrt().copysign(b));
+        let (r0, r1) = match q == 0.0 {
+            true => (0.0, 0.0),
+            false => (q / a, c / q),
+        };
+
+        let mut count = 0;
+        for root in [r0.min(r1), r0.max(r1)] {
+            if (0.0..=1.0).contains(&root) && (count == 0 || root > out[0]) {
+                out[count] = root;
+                count += 1;
+            }
+        }
+
+        return count;
+    }
+
+    let evaluate = |t: f64| coefficients.iter().rev().fold(0.0, |acc, &c| acc*t + c);
+
+    // Split [0, 1] at the roots of the derivative
+    let mut derivative = [0.0; 5];
+    for i in 0..degree {
+        derivative[i] = (i + 1) as f64 * coefficients[i + 1];
+    }
+
+    let mut bounds = [0.0; 7];
+    let inner = polynomial_roots(&derivative[..degree], (&mut bounds[1..6]).try_into().unwrap());
+    bounds[inner + 1] = 1.0;
+
+    let mut count = 0;
+    let mut low = bounds[0];
+    let mut f_low = evaluate(low);
+    for &high in bounds[1..inner + 2].iter() {
+        let f_high = evaluate(high);
+        let root = if f_low == 0.0 {
+            Some(low)
+        } else if (f_low < 0.0) != (f_high < 0.0) {
+            Some(monotonic_root(coefficients, low, high, f_low < 0.0))
+        } else {
+            None
+        };
+
+        if let Some(root) = root {
+            if count == 0 || root > out[count - 1] {
+                out[count] = root;
+                count += 1;
+            }
+        }
+
+        low = high;
+        f_low = f_high;
+    }
+
+    if f_low == 0.0 && (count == 0 || low > out[count - 1]) && count < out.len() {
+        out[count] = low;
+        count += 1;
+    }
+
+    count
+}
+
+/// Find the root of a polynomial that is monotonic and changes sign on [`low`, `high`]. `increasing` is true if the polynomial is negative at `low`.
+/// Newton iterations are used while they stay inside the bracket, otherwise the bracket is bisected.
+fn monotonic_root(coefficients: &[f64], mut low: f64, mut high: f64, increasing: bool) -> f64 {
+    let mut t = 0.5 * (low + high);
+    for _ in 0..100 {
+        let (f, df) = coefficients.iter().rev().fold((0.0, 0.0), |(f, df), &c| (f*t + c, df*t + f));
+        if f == 0.0 {
+            return t;
+        }
+
+        match (f < 0.0) == increasing {
+            true => { low = t; },
+            false => { high = t; },
+        }
+
+        let newton = t - (f / df);
+        let next = match newton > low && newton < high {
+            true => newton,
+            false => 0.5 * (low + high),
+        };
+
+        if (next - t).abs() <= 1e-12 || high - low <= 1e-12 {
+            return next;
+        }
+
+        t = next;
+    }
+
+    t
+}
+
+/// Return the parameter `t` of the point on the arc nearest to `p` and its squared distance from `p`
+/// 
+/// The nearest point is either an end point, or a point where `(arc(angle) - p) . arc'(angle) = 0`. The arc is split in parts of a quarter
+/// of a turn at most, and the tangent half-angle substitution turns this equation into a quartic on each part, which is solved with [polynomial_roots].
+fn closest_arc(p: Vec2, arc: &Line) -> (f32, f32) {
+    let Line::Arc { center, radius, rotation, start_angle, sweep_angle } = *arc else {
+        return (0.0, 0.0);
+    };
+
+    // `p` in the frame of the ellipse
+    let (sin, cos) = (rotation as f64).sin_cos();
+    let (dx, dy) = ((p[0] - center[0]) as f64, (p[1] - center[1]) as f64);
+    let (qx, qy) = ((cos * dx) + (sin * dy), (cos * dy) - (sin * dx));
+    let (a, b) = (radius[0] as f64, radius[1] as f64);
+    let squared_distance = |angle: f64| {
+        let x = a * angle.cos() - qx;
+        let y = b * angle.sin() - qy;
+        x*x + y*y
+    };
+
+    let (start, sweep) = (start_angle as f64, sweep_angle as f64);
+    let mut closest_t = 0.0;
+    let mut min_distance = squared_distance(start);
+    let end_distance = squared_distance(start + sweep);
+    if end_distance < min_distance {
+        closest_t = 1.0;
+        min_distance = end_distance;
+    }
+
+    if sweep == 0.0 {
+        return (closest_t as f32, min_distance as f32);
+    }
+
+    let parts = ((sweep.abs() / std::f64::consts::FRAC_PI_2).ceil() as usize).max(1);
+    let step = sweep / parts as f64;
+    let k = (step / 2.0).tan();
+    for i in 0..parts {
+        // With `u = tan((angle - angle0) / 2)`, `cos(angle)` and `sin(angle)` are `C(u) / (1 + u^2)` and `S(u) / (1 + u^2)`
+        let (s0, c0) = (start + step * i as f64).sin_cos();
+        let cos_u = [c0, -2.0*s0, -c0];
+        let sin_u = [s0, 2.0*c0, -s0];
+        let one_u = [1.0, 0.0, 1.0];
+
+        // (b^2 - a^2) sin(angle) cos(angle) + a qx sin(angle) - b qy cos(angle), multiplied by (1 + u^2)^2, with `u = k * t`
+        let mut quartic = [0.0; 5];
+        for i in 0..3 {
+            for j in 0..3 {
+                let linear = (a * qx * sin_u[i]) - (b * qy * cos_u[i]);
+                quartic[i + j] += ((b*b - a*a) * sin_u[i] * cos_u[j]) + (linear * one_u[j]);
+            }
+        }
+
+        let mut scale = 1.0;
+        for c in quartic.iter_mut() {
+            *c *= scale;
+            scale *= k;
+        }
+
+        let mut roots = [0.0; 5];
+        let count = polynomial_roots(&quartic, &mut roots);
+        for &root in roots[..count].iter() {
+            let t = (i as f64 + (2.0 * (k * root).atan() / step)) / parts as f64;
+            let distance = squared_distance(start + sweep * t);
+            if distance < min_distance {
+                min_distance = distance;
+                closest_t = t;
+            }
+        }
+    }
+
+    (closest_t as f32, min_distance as f32)
+}
+
+/// Return the first parameter `t` in [0, 1] where the arc going from `start` and turning by `sweep` reaches `angle` (modulo a full turn)
+fn arc_parameter(angle: f64, start: f64, sweep: f64) -> Option<f64> {
+    use std::f64::consts::TAU;
+    if sweep == 0.0 {
+        return None;
+    }
+
+    let offset = match sweep > 0.0 {
+        true => (angle - start).rem_euclid(TAU),
+        false => -(start - angle).rem_euclid(TAU),
+    };
+
+    let t = offset / sweep;
+    match t <= 1.0 {
+        true => Some(t),
+        false => None,
+    }
+}
+
+/// Return the sine and cosine of `angle`. Angles within the rounding error of a single precision multiple of a quarter turn
+/// are snapped to it, so that the end points of arcs such as half circles land exactly on their axis.
+fn quarter_sin_cos(angle: f64) -> (f64, f64) {
+    use std::f64::consts::FRAC_PI_2;
+    let quarter = (angle / FRAC_PI_2).round();
+    if (angle - quarter * FRAC_PI_2).abs() > 2e-6 * angle.abs().max(1.0) {
+        return angle.sin_cos();
+    }
+
+    match (quarter as i64).rem_euclid(4) {
+        0 => (0.0, 1.0),
+        1 => (1.0, 0.0),
+        2 => (0.0, -1.0),
+        _ => (-1.0, 0.0),
+    }
+}
+
+/// Number of equal parts needed to split a curve, where `parts` is the exact (fractional) number of parts
+fn segment_count(parts: f32) -> usize {
+    match parts.is_nan() {
+        true => 1,
+        false => (parts.ceil() as usize).clamp(1, MAX_SEGMENTS),
+    }
+}
+
+/// Scale an arc on the x and y axis. Returns the radius, rotation, start angle and sweep angle of the scaled arc.
+fn transform_arc(radius: Vec2, rotation: f32, start_angle: f32, sweep_angle: f32, matrix: [f64; 4]) -> (Vec2, f32, f32, f32) {
+    // Singular value decomposition of `matrix * rotation * radius`, so that the transformed arc is `rotation(phi) * [sx, sy] * rotation(theta)`
+    let (sin, cos) = (rotation as f64).sin_cos();
+    let (a, b) = (radius[0] as f64, radius[1] as f64);
+    let [n00, n01, n10, n11] = matrix;
+    let (m00, m01) = ((n00*cos + n01*sin)*a, (n01*cos - n00*sin)*b);
+    let (m10, m11) = ((n10*cos + n11*sin)*a, (n11*cos - n10*sin)*b);
+
+    let (e, f, g, h) = ((m00 + m11) / 2.0, (m00 - m11) / 2.0, (m10 + m01) / 2.0, (m10 - m01) / 2.0);
+    let (q, r) = ((e*e + h*h).sqrt(), (f*f + g*g).sqrt());
+    let (sx, sy) = (q + r, q - r);
+    let (a1, a2) = (g.atan2(f), h.atan2(e));
+    let (theta, phi) = ((a2 - a1) / 2.0, (a2 + a1) / 2.0);
+
+    let start = start_angle as f64 + theta;
+    let radius = vec2(sx as f32, sy.abs() as f32);
+    match sy < 0.0 {
+        // A negative radius mirrors the arc
+        true => (radius, phi as f32, -start as f32, -sweep_angle),
+        false => (radius, phi as f32, start as f32, sweep_angle),
+    }
+}
+
 fn align_quadratic(y: f32, start: &mut Vec2, end: &mut Vec2, control: &mut Vec2) {
     let p = vec2(0.0, y);
     *start = *start - p;
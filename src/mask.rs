@@ -0,0 +1,207 @@
+//! Signed distance field from a raster mask, using the 8 points sequential euclidean distance transform (8SSEDT).
+//! Instead of the distance, each pixel stores the position of the nearest edge point. Edge points are found with subpixel
+//! precision using the coverage of the anti-aliased pixels.
+use crate::math::{Point, Vec2, vec2};
+use crate::{DistanceUnits, SdfRaster, Spread, pixel_range, pixel_size};
+
+/// Rasterize the sdf of a grayscale mask
+///
+/// # Arguments
+///
+/// * `width`: Width (in pixels) of the mask and of the output raster
+/// * `height`: Height (in pixels) of the mask and of the output raster
+/// * `spread`: Control how the gradient in the sdf spread. Same as [sdf_generate](crate::sdf_generate)
+///   The distances are measured in the normalized space of the mask, unless `spread` is a [Spread::Pixels].
+/// * `mask`: Coverage of each pixel, row by row. `0` is outside the shape and `255` is inside the shape.
+///   The values in between are anti-aliased edges, and are used to place the edge with subpixel precision.
+///
+/// # Return
+///
+/// Returns a 1 channel [SdfRaster] with the same size as the mask. Values use the same convention as [sdf_generate](crate::sdf_generate):
+/// 0.0 (outside) to 1.0 (inside), with 0.5 being directly on the edge.
+///
+/// # Panics
+///
+/// Panics if `mask` is smaller than `width * height`
+///
+/// # Note
+///
+/// * The area outside the mask is not considered, so shapes touching the borders of the mask have no edge on that side.
+pub fn sdf_from_mask(width: u32, height: u32, spread: impl Into<Spread>, mask: &[u8]) -> SdfRaster {
+    let (spread, units) = spread.into().resolve(DistanceUnits::Normalized);
+    let w = width as usize;
+    let h = height as usize;
+    if mask.len() < w * h {
+        panic!("Mask size ({:?}) is smaller than {:?}x{:?}", mask.len(), width, height);
+    }
+
+    let coverage = |x: usize, y: usize| mask[x + (w * y)] as f32 / 255.0;
+    let inside = |x: usize, y: usize| mask[x + (w * y)] >= 128;
+    let hard = |x: usize, y: usize| mask[x + (w * y)] == 0 || mask[x + (w * y)] == 255;
+
+    // Distances are measured in the same space as `sdf_generate`
+    let scale = pixel_size(width, height, units);
+    let center = |x: usize, y: usize| vec2(x as f32 + 0.5, y as f32 + 0.5);
+
+    // Step 1: Find the edge points
+    let mut nearest: Vec<Option<Point>> = vec![None; w * h];
+    for y in 0..h {
+        for x in 0..w {
+            let a = coverage(x, y);
+            let edge = if a > 0.0 && a < 1.0 {
+                // Anti-aliased pixel. The edge crosses the pixel perpendicular to the coverage gradient
+                let normal = coverage_gradient(mask, w, h, x, y);
+                Some(center(x, y) + normal * edge_offset(normal, a))
+            } else {
+                // Hard edge. The edge is half way between two neighbours on different sides
+                let side = inside(x, y);
+                let neighbours = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+                neighbours.iter()
+                    .map(|&(dx, dy)| (x as isize + dx, y as isize + dy))
+                    .filter(|&(nx, ny)| nx >= 0 && ny >= 0 && (nx as usize) < w && (ny as usize) < h)
+                    .map(|(nx, ny)| (nx as usize, ny as usize))
+                    .find(|&(nx, ny)| hard(nx, ny) && inside(nx, ny) != side)
+                    .map(|(nx, ny)| (center(x, y) + center(nx, ny)) * 0.5)
+            };
+
+            nearest[x + (w * y)] = edge;
+        }
+    }
+
+    // Step 2: Propagate the nearest edge points
+    let distance = |p: Point, edge: Option<Point>| match edge {
+        Some(e) => ((p - e) * scale).length(),
+        None => f32::MAX
+    };
+
+    let check = |nearest: &mut [Option<Point>], x: usize, y: usize, nx: isize, ny: isize| {
+        if nx < 0 || ny < 0 || nx as usize >= w || ny as usize >= h {
+            return;
+        }
+
+        let candidate = nearest[nx as usize + (w * ny as usize)];
+        let index = x + (w * y);
+        let p = center(x, y);
+        if candidate.is_some() && distance(p, candidate) < distance(p, nearest[index]) {
+            nearest[index] = candidate;
+        }
+    };
+
+    for y in 0..h {
+        for x in 0..w {
+            let (xi, yi) = (x as isize, y as isize);
+            check(&mut nearest, x, y, xi - 1, yi);
+            check(&mut nearest, x, y, xi, yi - 1);
+            check(&mut nearest, x, y, xi - 1, yi - 1);
+            check(&mut nearest, x, y, xi + 1, yi - 1);
+        }
+
+        for x in (0..w).rev() {
+            check(&mut nearest, x, y, x as isize + 1, y as isize);
+        }
+    }
+
+    for y in (0..h).rev() {
+        for x in (0..w).rev() {
+            let (xi, yi) = (x as isize, y as isize);
+            check(&mut nearest, x, y, xi + 1, yi);
+            check(&mut nearest, x, y, xi, yi + 1);
+            check(&mut nearest, x, y, xi - 1, yi + 1);
+            check(&mut nearest, x, y, xi + 1, yi + 1);
+        }
+
+        for x in 0..w {
+            check(&mut nearest, x, y, x as isize - 1, y as isize);
+        }
+    }
+
+    // Step 3: Convert the distances
+    let mut buffer = vec![0.0; w * h];
+    for y in 0..h {
+        for x in 0..w {
+            let index = x + (w * y);
+            let d = distance(center(x, y), nearest[index]);
+            let value = match inside(x, y) {
+                true => 0.5 + (d * spread),
+                false => 0.5 - (d * spread),
+            };
+
+            buffer[index] = value.clamp(0.0, 1.0);
+        }
+    }
+
+    SdfRaster {
+        width,
+        height,
+        channels: 1,
+        buffer,
+        pixel_range: pixel_range(width, height, spread, units),
+    }
+}
+
+/// Load a mask from an image file and rasterize its sdf with [sdf_from_mask].
+///
+/// The alpha channel of the image is used as the mask if the image has one, otherwise the luminance is used.
+///
+/// # Arguments
+///
+/// * `mask_name`: Name of the image file
+/// * `spread`: Control how the gradient in the sdf spread. Same as [sdf_generate](crate::sdf_generate)
+///
+/// # Return
+///
+/// * Returns the error of the `image.open` function if the image could not be loaded
+///
+/// # Feature
+///
+/// * Requires the `export` feature with the used file format subfeature (ex: png, jpeg, etc)
+#[cfg(feature="export")]
+pub fn sdf_from_mask_file(mask_name: &str, spread: impl Into<Spread>) -> image::ImageResult<SdfRaster> {
+    let img = image::open(mask_name)?;
+    let (width, height) = (img.width(), img.height());
+    let mask: Vec<u8> = match img.color().has_alpha() {
+        true => img.to_luma_alpha8().pixels().map(|p| p[1]).collect(),
+        false => img.to_luma8().into_raw(),
+    };
+
+    Ok(sdf_from_mask(width, height, spread, &mask))
+}
+
+/// Sobel gradient of the mask coverage at (`x`, `y`), normalized. Points toward the inside of the shape.
+fn coverage_gradient(mask: &[u8], width: usize, height: usize, x: usize, y: usize) -> Vec2 {
+    let sample = |dx: isize, dy: isize| {
+        let sx = (x as isize + dx).clamp(0, width as isize - 1) as usize;
+        let sy = (y as isize + dy).clamp(0, height as isize - 1) as usize;
+        mask[sx + (width * sy)] as f32
+    };
+
+    let gx = (sample(1, -1) + 2.0 * sample(1, 0) + sample(1, 1)) - (sample(-1, -1) + 2.0 * sample(-1, 0) + sample(-1, 1));
+    let gy = (sample(-1, 1) + 2.0 * sample(0, 1) + sample(1, 1)) - (sample(-1, -1) + 2.0 * sample(0, -1) + sample(1, -1));
+    let gradient = vec2(gx, gy);
+    match gradient.length() > 0.0 {
+        true => gradient.normalize(),
+        false => vec2(0.0, 0.0)
+    }
+}
+
+/// Distance (in pixels) between the center of a pixel with a coverage of `a` and the edge crossing it, along `normal`.
+/// Assumes the edge is a straight line perpendicular to `normal`. From "Anti-aliased Euclidean distance transform" by Stefan Gustavson.
+fn edge_offset(normal: Vec2, a: f32) -> f32 {
+    let (mut gx, mut gy) = (normal[0].abs(), normal[1].abs());
+    if gx == 0.0 || gy == 0.0 {
+        return 0.5 - a;
+    }
+
+    if gx < gy {
+        std::mem::swap(&mut gx, &mut gy);
+    }
+
+    let a1 = 0.5 * gy / gx;
+    if a < a1 {
+        0.5 * (gx + gy) - (2.0 * gx * gy * a).sqrt()
+    } else if a < 1.0 - a1 {
+        (0.5 - a) * gx
+    } else {
+        -0.5 * (gx + gy) + (2.0 * gx * gy * (1.0 - a)).sqrt()
+    }
+}
@@ -57,9 +57,9 @@ impl Vec2 {
     pub fn normalize(&self) -> Self {
         let len = self.length();
         if len == 0.0 {
-            vec2(0.0, 1.0);
+            return vec2(0.0, 1.0);
         }
-        return vec2(self[0]/len, self[1]/len);
+        vec2(self[0]/len, self[1]/len)
     }
 
     #[inline(always)]
@@ -0,0 +1,286 @@
+//! Edge coloring and distance functions used by the multi-channel signed distance field generator.
+//! The algorithm is a (simplified) port of msdfgen by Viktor Chlumský: <https://github.com/Chlumsky/msdfgen>
+use crate::line::Line;
+use crate::math::{Vec2, vec2};
+use crate::{FillRule, scanline, scanline_scan};
+use crate::shape::split_contours;
+
+const RED: u8 = 0b001;
+const GREEN: u8 = 0b010;
+const BLUE: u8 = 0b100;
+const CYAN: u8 = GREEN | BLUE;
+const WHITE: u8 = RED | GREEN | BLUE;
+
+/// Two edges are considered to form a corner if the sine of the angle between them is higher than this value (~3 radians).
+const CORNER_THRESHOLD: f32 = 0.141_12;
+
+/// Distance used to probe the side of a contour that is inside the shape
+const ORIENTATION_PROBE: f32 = 1e-4;
+
+/// A line with the color channels it contributes to
+pub(crate) struct Edge {
+    line: Line,
+    color: u8,
+    /// `1.0` if the inside of the shape is on the left side of the edge, `-1.0` otherwise
+    orientation: f32,
+}
+
+/// Distance between a point and an edge
+#[derive(Copy, Clone)]
+struct EdgeDistance {
+    /// Signed distance to the edge. Positive values are inside the shape.
+    distance: f32,
+    /// Signed distance to the edge extended past its end points, so that the channels keep sharp corners
+    pseudo_distance: f32,
+    /// How orthogonal the edge is to the point. Used to break ties between edges sharing a point.
+    dot: f32,
+}
+
+impl EdgeDistance {
+    const FAR: EdgeDistance = EdgeDistance { distance: f32::MAX, pseudo_distance: f32::MAX, dot: 1.0 };
+
+    fn is_closer(&self, other: &EdgeDistance) -> bool {
+        let d1 = self.distance.abs();
+        let d2 = other.distance.abs();
+        d1 < d2 || (d1 == d2 && self.dot < other.dot)
+    }
+}
+
+/// Split `lines` into contours and assign a color to each of their edges.
+/// The lines are split in contours everywhere the end of a line is not the start of the next one.
+pub(crate) fn color_edges(lines: &[Line]) -> Vec<Edge> {
+    let mut edges = Vec::with_capacity(lines.len());
+    for contour in split_contours(lines) {
+        let orientation = contour_orientation(contour, lines);
+        color_contour(contour, orientation, &mut edges);
+    }
+
+    edges
+}
+
+/// Return the signed pseudo distance of `p` for the red, green and blue channels. Positive values are inside the shape.
+pub(crate) fn pixel_distances(edges: &[Edge], p: Vec2) -> [f32; 3] {
+    let mut nearest = [EdgeDistance::FAR; 3];
+    let mut out = [-f32::MAX; 3];
+
+    for edge in edges {
+        let distance = edge_distance(edge, p);
+        for (channel, color) in [RED, GREEN, BLUE].iter().enumerate() {
+            if edge.color & color != 0 && distance.is_closer(&nearest[channel]) {
+                nearest[channel] = distance;
+                out[channel] = distance.pseudo_distance;
+            }
+        }
+    }
+
+    out
+}
+
+/// Find the pixels where the interpolation between two neighbours would create an artifact and replace their color channels by their median.
+/// `threshold_x` and `threshold_y` are the value difference expected between two neighbours one pixel away from an edge.
+pub(crate) fn correct_errors(buffer: &mut [f32], width: u32, height: u32, channels: usize, threshold_x: f32, threshold_y: f32) {
+    let width = width as usize;
+    let height = height as usize;
+    let pixel = |buffer: &[f32], x: usize, y: usize| {
+        let index = (x + (width * y)) * channels;
+        [buffer[index], buffer[index+1], buffer[index+2]]
+    };
+
+    let threshold_xy = (threshold_x * threshold_x + threshold_y * threshold_y).sqrt();
+    let mut clashes = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let a = pixel(buffer, x, y);
+            let clash = (x > 0 && detect_clash(a, pixel(buffer, x-1, y), threshold_x))
+                || (x + 1 < width && detect_clash(a, pixel(buffer, x+1, y), threshold_x))
+                || (y > 0 && detect_clash(a, pixel(buffer, x, y-1), threshold_y))
+                || (y + 1 < height && detect_clash(a, pixel(buffer, x, y+1), threshold_y))
+                || (x > 0 && y > 0 && detect_clash(a, pixel(buffer, x-1, y-1), threshold_xy))
+                || (x + 1 < width && y > 0 && detect_clash(a, pixel(buffer, x+1, y-1), threshold_xy))
+                || (x > 0 && y + 1 < height && detect_clash(a, pixel(buffer, x-1, y+1), threshold_xy))
+                || (x + 1 < width && y + 1 < height && detect_clash(a, pixel(buffer, x+1, y+1), threshold_xy));
+
+            if clash {
+                clashes.push(x + (width * y));
+            }
+        }
+    }
+
+    for index in clashes {
+        let index = index * channels;
+        let median = median([buffer[index], buffer[index+1], buffer[index+2]]);
+        buffer[index..index+3].fill(median);
+    }
+}
+
+/// Return the median of three values
+pub(crate) fn median(v: [f32; 3]) -> f32 {
+    v[0].min(v[1]).max(v[0].max(v[1]).min(v[2]))
+}
+
+/// Check if the channels of two neighbouring pixels `a` and `b` would clash when interpolated
+fn detect_clash(a: [f32; 3], b: [f32; 3], threshold: f32) -> bool {
+    // Sort the channels so that the pairs go from the biggest to the smallest difference
+    let [mut a0, mut a1, mut a2] = a;
+    let [mut b0, mut b1, mut b2] = b;
+    if (b0 - a0).abs() < (b1 - a1).abs() {
+        std::mem::swap(&mut a0, &mut a1);
+        std::mem::swap(&mut b0, &mut b1);
+    }
+    if (b1 - a1).abs() < (b2 - a2).abs() {
+        std::mem::swap(&mut a1, &mut a2);
+        std::mem::swap(&mut b1, &mut b2);
+        if (b0 - a0).abs() < (b1 - a1).abs() {
+            std::mem::swap(&mut a0, &mut a1);
+            std::mem::swap(&mut b0, &mut b1);
+        }
+    }
+
+    (b1 - a1).abs() >= threshold
+        && !(b0 == b1 && b0 == b2)          // Ignore the pixels that were already equalized
+        && (a2 - 0.5).abs() >= (b2 - 0.5).abs() // Only flag the pixel farther from the edge
+}
+
+/// Find on which side of `contour` the inside of the shape is
+fn contour_orientation(contour: &[Line], lines: &[Line]) -> f32 {
+    let longest = contour.iter().fold(None, |acc: Option<(f32, &Line)>, line| {
+        let length = (line.end() - line.start()).length();
+        match acc {
+            Some((l, _)) if l >= length => acc,
+            _ => Some((length, line))
+        }
+    });
+
+    let line = match longest {
+        Some((_, line)) => line,
+        None => { return 1.0; }
+    };
+
+    let direction = line.direction(0.5);
+    let normal = vec2(-direction[1], direction[0]).normalize();
+    let probe = line.point(0.5) + normal * ORIENTATION_PROBE;
+    match scanline_scan(&scanline(probe[1], lines, FillRule::EvenOdd), probe[0]) {
+        true => 1.0,
+        false => -1.0
+    }
+}
+
+fn color_contour(contour: &[Line], orientation: f32, edges: &mut Vec<Edge>) {
+    let count = contour.len();
+    let mut push = |line: Line, color: u8| edges.push(Edge { line, color, orientation });
+
+    let mut corners = Vec::new();
+    if let Some(last) = contour.last() {
+        let mut previous = last.direction(1.0).normalize();
+        for (i, line) in contour.iter().enumerate() {
+            let direction = line.direction(0.0).normalize();
+            if previous.dot(direction) <= 0.0 || previous.cross(direction).abs() > CORNER_THRESHOLD {
+                corners.push(i);
+            }
+            previous = line.direction(1.0).normalize();
+        }
+    }
+
+    match corners.len() {
+        0 => {
+            // Smooth contour
+            for line in contour {
+                push(*line, WHITE);
+            }
+        },
+        1 => {
+            // Teardrop. The contour must be split in at least 3 edges with different colors
+            let first = switch_color(WHITE, 0);
+            let colors = [first, WHITE, switch_color(first, 0)];
+            let corner = corners[0];
+            if count >= 3 {
+                for i in 0..count {
+                    let index = (corner + i) % count;
+                    push(contour[index], colors[(1 + symmetrical_trichotomy(i, count)) as usize]);
+                }
+            } else {
+                let mut parts = [None; 6];
+                let [a, b, c] = split_in_thirds(&contour[0]);
+                parts[3*corner] = Some(a);
+                parts[1+3*corner] = Some(b);
+                parts[2+3*corner] = Some(c);
+
+                let part_colors = match count >= 2 {
+                    true => {
+                        let [a, b, c] = split_in_thirds(&contour[1]);
+                        parts[3-3*corner] = Some(a);
+                        parts[4-3*corner] = Some(b);
+                        parts[5-3*corner] = Some(c);
+                        [colors[0], colors[0], colors[1], colors[1], colors[2], colors[2]]
+                    },
+                    false => [colors[0], colors[1], colors[2], 0, 0, 0]
+                };
+
+                for (part, color) in parts.iter().zip(part_colors.iter()) {
+                    if let Some(line) = part {
+                        push(*line, *color);
+                    }
+                }
+            }
+        },
+        corner_count => {
+            // Switch color at every corner
+            let start = corners[0];
+            let mut spline = 0;
+            let mut color = switch_color(WHITE, 0);
+            let initial_color = color;
+            for i in 0..count {
+                let index = (start + i) % count;
+                if spline + 1 < corner_count && corners[spline + 1] == index {
+                    spline += 1;
+                    let banned = match spline == corner_count - 1 {
+                        true => initial_color,
+                        false => 0
+                    };
+                    color = switch_color(color, banned);
+                }
+                push(contour[index], color);
+            }
+        }
+    }
+}
+
+/// Return the next color in the cycle cyan -> magenta -> yellow, avoiding the channels in `banned`
+fn switch_color(color: u8, banned: u8) -> u8 {
+    let combined = color & banned;
+    if combined == RED || combined == GREEN || combined == BLUE {
+        return combined ^ WHITE;
+    }
+
+    if color == 0 || color == WHITE {
+        return CYAN;
+    }
+
+    let shifted = color << 1;
+    (shifted | (shifted >> 3)) & WHITE
+}
+
+/// Map `position` in `0..n` to -1, 0 or 1, symmetrically
+fn symmetrical_trichotomy(position: usize, n: usize) -> i32 {
+    (3.0 + 2.875 * position as f32 / (n - 1) as f32 - 1.4375 + 0.5) as i32 - 3
+}
+
+fn split_in_thirds(line: &Line) -> [Line; 3] {
+    let (a, rest) = line.split(1.0 / 3.0);
+    let (b, c) = rest.split(0.5);
+    [a, b, c]
+}
+
+fn edge_distance(edge: &Edge, p: Vec2) -> EdgeDistance {
+    let closest = edge.line.closest_point(p);
+    let dot = match closest.distance != 0.0 {
+        true => closest.tangent.dot((p - closest.point).normalize()).abs(),
+        false => 0.0
+    };
+
+    EdgeDistance {
+        distance: closest.distance * edge.orientation,
+        pseudo_distance: closest.pseudo_distance * edge.orientation,
+        dot,
+    }
+}
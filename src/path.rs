@@ -0,0 +1,313 @@
+//! Parser for the path data of SVG (the `d` attribute of a `<path>` element).
+//!
+//! Every command is supported (`M`, `L`, `H`, `V`, `C`, `S`, `Q`, `T`, `A`, `Z`), in absolute and relative form.
+//! Arcs are converted to [Line::Arc]. Each subpath is closed, as a filled shape would be.
+use std::fmt;
+use crate::line::Line;
+use crate::math::{Point, vec2};
+use crate::shape::Shape;
+
+/// The rectangle of the user space of a SVG that is visible, from the `viewBox` attribute of the `<svg>` element
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ViewBox {
+    /// Left side of the rectangle
+    pub x: f32,
+    /// Top side of the rectangle
+    pub y: f32,
+    /// Width of the rectangle
+    pub width: f32,
+    /// Height of the rectangle
+    pub height: f32,
+}
+
+impl ViewBox {
+
+    /// Parse the value of a `viewBox` attribute (ex: `"0 0 24 24"`). Returns `None` if the value is not four numbers or if the size is not positive.
+    pub fn parse(value: &str) -> Option<ViewBox> {
+        let mut parser = Parser { data: value.as_bytes(), position: 0 };
+        let mut values = [0.0; 4];
+        for value in values.iter_mut() {
+            *value = parser.number().ok()?;
+        }
+
+        parser.skip_separators();
+        let [x, y, width, height] = values;
+        match parser.position == parser.data.len() && width > 0.0 && height > 0.0 {
+            true => Some(ViewBox { x, y, width, height }),
+            false => None,
+        }
+    }
+
+    /// Map the lines from the user space of the SVG to the normalized space of a sdf, where the view box goes from `0.0` to `1.0` on both axis.
+    /// The view box is stretched if it is not square, so the sdf should have the same aspect ratio as the view box.
+    pub fn normalize(&self, lines: &mut [Line]) {
+        for line in lines.iter_mut() {
+            line.normalize_with_offset(self.x, self.y, self.width, self.height);
+        }
+    }
+
+}
+
+/// Error returned when parsing an invalid SVG path. `position` is the byte offset of the error in the path data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PathError {
+    /// A character that is not a command nor a number
+    UnexpectedCharacter { position: usize, character: char },
+    /// A command is missing some of its numbers
+    MissingNumber { position: usize },
+    /// A number or an arc flag is not valid
+    InvalidNumber { position: usize },
+    /// The path does not start with a move to (`M` or `m`)
+    MissingMoveTo { position: usize },
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PathError::UnexpectedCharacter { position, character } => write!(f, "Unexpected character {:?} at {}", character, position),
+            PathError::MissingNumber { position } => write!(f, "Missing number at {}", position),
+            PathError::InvalidNumber { position } => write!(f, "Invalid number at {}", position),
+            PathError::MissingMoveTo { position } => write!(f, "Path must start with a move to (at {})", position),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// Parse SVG path data into a list of lines, in the user space of the SVG. See [parse_svg_path_normalized] to map the lines to a view box.
+///
+/// # Example
+///
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+///
+/// let lines = sdf::parse_svg_path("M 0.5 0.1 L 0.9 0.9 Q 0.5 0.6 0.1 0.9 Z").unwrap();
+/// assert_eq!(lines.len(), 3);
+/// ```
+pub fn parse_svg_path(d: &str) -> Result<Vec<Line>, PathError> {
+    let mut parser = Parser { data: d.as_bytes(), position: 0 };
+    let mut shape = Shape::new();
+    let mut current = vec2(0.0, 0.0);
+    let mut start = vec2(0.0, 0.0);
+    // Control point reflected by the `S` and `T` commands, and the command that set it
+    let mut last_control: Option<(u8, Point)> = None;
+    let mut command: Option<u8> = None;
+
+    loop {
+        parser.skip_separators();
+        let Some(&next) = parser.data.get(parser.position) else {
+            break;
+        };
+
+        if next.is_ascii_alphabetic() {
+            if !b"MmLlHhVvCcSsQqTtAaZz".contains(&next) {
+                return Err(parser.unexpected());
+            }
+
+            if command.is_none() && !matches!(next, b'M' | b'm') {
+                return Err(PathError::MissingMoveTo { position: parser.position });
+            }
+
+            parser.position += 1;
+            command = Some(next);
+        } else if matches!(command, None | Some(b'Z' | b'z')) {
+            // Numbers must follow a command that takes numbers
+            return match command {
+                None => Err(PathError::MissingMoveTo { position: parser.position }),
+                Some(_) => Err(parser.unexpected()),
+            };
+        }
+
+        let Some(c) = command else { unreachable!() };
+        let relative = c.is_ascii_lowercase();
+        let origin = match relative {
+            true => current,
+            false => vec2(0.0, 0.0),
+        };
+
+        let mut control = None;
+        match c.to_ascii_uppercase() {
+            b'M' => {
+                let p = origin + parser.point()?;
+                shape.move_to(p[0], p[1]);
+                current = p;
+                start = p;
+
+                // Numbers after a move to are line to
+                command = Some(if relative { b'l' } else { b'L' });
+            },
+            b'L' => {
+                current = origin + parser.point()?;
+                shape.line_to(current[0], current[1]);
+            },
+            b'H' => {
+                current = vec2(origin[0] + parser.number()?, current[1]);
+                shape.line_to(current[0], current[1]);
+            },
+            b'V' => {
+                current = vec2(current[0], origin[1] + parser.number()?);
+                shape.line_to(current[0], current[1]);
+            },
+            b'C' | b'S' => {
+                let first = match c.to_ascii_uppercase() {
+                    b'C' => origin + parser.point()?,
+                    _ => reflect(last_control, b"CcSs", current),
+                };
+                let second = origin + parser.point()?;
+                let end = origin + parser.point()?;
+                shape.cubic_to(first[0], first[1], second[0], second[1], end[0], end[1]);
+                control = Some(second);
+                current = end;
+            },
+            b'Q' | b'T' => {
+                let quad_control = match c.to_ascii_uppercase() {
+                    b'Q' => origin + parser.point()?,
+                    _ => reflect(last_control, b"QqTt", current),
+                };
+                let end = origin + parser.point()?;
+                shape.quad_to(quad_control[0], quad_control[1], end[0], end[1]);
+                control = Some(quad_control);
+                current = end;
+            },
+            b'A' => {
+                let radius = parser.point()?;
+                let rotation = parser.number()?.to_radians();
+                let large_arc = parser.flag()?;
+                let sweep = parser.flag()?;
+                let end = origin + parser.point()?;
+                shape.arc_to(radius.abs(), rotation, large_arc, sweep, end[0], end[1]);
+                current = end;
+            },
+            _ => {
+                // Close path
+                shape.close();
+                current = start;
+            }
+        }
+
+        last_control = control.map(|p| (c, p));
+    }
+
+    shape.close();
+    Ok(shape.into_lines())
+}
+
+/// Parse SVG path data into a list of lines, and map the lines from `view_box` to the normalized space of a sdf. See [ViewBox::normalize]
+///
+/// # Example
+///
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+///
+/// let view_box = sdf::ViewBox::parse("0 0 24 24").unwrap();
+/// let lines = sdf::parse_svg_path_normalized("M12 2a10 10 0 1 0 0 20a10 10 0 1 0 0-20z", view_box).unwrap();
+/// let sdf = sdf::sdf_generate(64, 64, 2, 8.0, &lines);
+/// ```
+pub fn parse_svg_path_normalized(d: &str, view_box: ViewBox) -> Result<Vec<Line>, PathError> {
+    let mut lines = parse_svg_path(d)?;
+    view_box.normalize(&mut lines);
+    Ok(lines)
+}
+
+/// Reflect the last control point around the current point if it was set by one of `commands`, or return the current point
+fn reflect(last_control: Option<(u8, Point)>, commands: &[u8], current: Point) -> Point {
+    match last_control {
+        Some((command, control)) if commands.contains(&command) => current * 2.0 - control,
+        _ => current,
+    }
+}
+
+pub(crate) struct Parser<'a> {
+    pub(crate) data: &'a [u8],
+    pub(crate) position: usize,
+}
+
+impl Parser<'_> {
+
+    pub(crate) fn skip_separators(&mut self) {
+        while let Some(c) = self.data.get(self.position) {
+            match c.is_ascii_whitespace() || *c == b',' {
+                true => { self.position += 1; },
+                false => break,
+            }
+        }
+    }
+
+    fn unexpected(&self) -> PathError {
+        let character = std::str::from_utf8(&self.data[self.position..]).ok()
+            .and_then(|s| s.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+
+        PathError::UnexpectedCharacter { position: self.position, character }
+    }
+
+    /// Parse a number. Numbers can be written without separators when there is no ambiguity (ex: `"0.5.5-1"` is `0.5`, `0.5`, `-1`).
+    pub(crate) fn number(&mut self) -> Result<f32, PathError> {
+        self.skip_separators();
+        let start = self.position;
+        let digits = |parser: &mut Self| {
+            let first = parser.position;
+            while parser.data.get(parser.position).is_some_and(|c| c.is_ascii_digit()) {
+                parser.position += 1;
+            }
+            parser.position > first
+        };
+
+        if matches!(self.data.get(self.position), Some(b'+' | b'-')) {
+            self.position += 1;
+        }
+
+        let mut valid = digits(self);
+        if self.data.get(self.position) == Some(&b'.') {
+            self.position += 1;
+            valid |= digits(self);
+        }
+
+        if !valid {
+            self.position = start;
+            return match self.data.get(start) {
+                None => Err(PathError::MissingNumber { position: start }),
+                Some(c) if c.is_ascii_alphabetic() => Err(PathError::MissingNumber { position: start }),
+                Some(_) => Err(PathError::InvalidNumber { position: start }),
+            };
+        }
+
+        // Exponent, only if it is followed by digits (`e` is not a command)
+        if matches!(self.data.get(self.position), Some(b'e' | b'E')) {
+            let mantissa_end = self.position;
+            self.position += 1;
+            if matches!(self.data.get(self.position), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+
+            if !digits(self) {
+                self.position = mantissa_end;
+            }
+        }
+
+        std::str::from_utf8(&self.data[start..self.position]).ok()
+            .and_then(|number| number.parse::<f32>().ok())
+            .ok_or(PathError::InvalidNumber { position: start })
+    }
+
+    pub(crate) fn point(&mut self) -> Result<Point, PathError> {
+        Ok(vec2(self.number()?, self.number()?))
+    }
+
+    /// Parse an arc flag. Flags are a single `0` or `1`, and do not need a separator (ex: `"a1 1 0 01.5.5"`).
+    fn flag(&mut self) -> Result<bool, PathError> {
+        self.skip_separators();
+        let position = self.position;
+        let flag = match self.data.get(position) {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            None => return Err(PathError::MissingNumber { position }),
+            Some(c) if c.is_ascii_alphabetic() => return Err(PathError::MissingNumber { position }),
+            Some(_) => return Err(PathError::InvalidNumber { position }),
+        };
+
+        self.position += 1;
+        Ok(flag)
+    }
+
+}
@@ -0,0 +1,239 @@
+use crate::line::Line;
+use crate::math::{Point, Vec2, vec2};
+use crate::shape::lines_bounds;
+use crate::{DistanceUnits, SdfRaster, SdfSettings, Spread, clean_lines, distance_space_lines, for_each_row_with, pad_lines, padding_transform, pixel_range, pixel_size, scanline, scanline_scan, sdf_value};
+
+/// Maximum number of cells on each axis of the grid
+const MAX_GRID_SIZE: usize = 64;
+
+/// A shape with a spatial acceleration structure built over its lines.
+///
+/// [sdf_generate](crate::sdf_generate) computes the distance between every pixel and every line of a shape. For shapes with a lot
+/// of lines (complex icons, CJK glyphs), most of this work is wasted on lines that are too far away to change the pixel value.
+/// A prepared shape sorts its lines in a uniform grid, so that only the lines near a pixel are evaluated. Lines farther than
+/// the `spread` cutoff are skipped. The output is identical to [sdf_generate](crate::sdf_generate).
+///
+/// The grid is built once in the shape coordinates and can be reused for any output size, padding or spread.
+///
+/// # Example
+///
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+///
+/// fn generate_icons(lines: &[sdf::Line]) -> (sdf::SdfRaster, sdf::SdfRaster) {
+///     let shape = sdf::PreparedShape::new(lines);
+///     (shape.sdf_generate(32, 32, 2, 8.0), shape.sdf_generate(128, 128, 4, 8.0))
+/// }
+/// ```
+pub struct PreparedShape {
+    lines: Vec<Line>,
+    grid: Grid,
+}
+
+/// Uniform grid storing the index of the lines overlapping each cell
+struct Grid {
+    min: Point,
+    cell_size: Vec2,
+    columns: usize,
+    rows: usize,
+    /// Cell `i` lines are `indices[offsets[i]..offsets[i+1]]`
+    offsets: Vec<usize>,
+    indices: Vec<usize>,
+}
+
+impl PreparedShape {
+
+    /// Builds the acceleration structure over `lines`. The lines must be normalized, same as [sdf_generate](crate::sdf_generate).
+    pub fn new(lines: &[Line]) -> PreparedShape {
+        let lines = clean_lines(lines).into_owned();
+        PreparedShape {
+            grid: Grid::new(&lines),
+            lines,
+        }
+    }
+
+    /// Returns the lines of the shape. The lines that cannot be rendered are removed, and the straight curves are replaced by lines.
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    /// Rasterize the shape. Same as [sdf_generate](crate::sdf_generate).
+    pub fn sdf_generate(&self, width: u32, height: u32, padding: i32, spread: impl Into<Spread>) -> SdfRaster {
+        self.sdf_generate_with_settings(width, height, padding, spread, SdfSettings::default())
+    }
+
+    /// Rasterize the shape. Same as [sdf_generate_with_settings](crate::sdf_generate_with_settings).
+    pub fn sdf_generate_with_settings(&self, width: u32, height: u32, padding: i32, spread: impl Into<Spread>, settings: SdfSettings) -> SdfRaster {
+        let (spread, units) = spread.into().resolve(settings.units);
+        let settings = SdfSettings { units, ..settings };
+        let padded_lines = distance_space_lines(width, height, settings.units, pad_lines(width, height, padding, &self.lines));
+        let lines: &[Line] = &padded_lines;
+        let bounds: Vec<(Point, Point)> = lines.iter().map(|line| line.bounds()).collect();
+        let (offset, scale) = padding_transform(width, height, padding);
+
+        // Past this distance, the pixel value is always clamped to 0.0 (or 1.0 inside the shape)
+        let cutoff = match spread > 0.0 {
+            true => (0.5 / spread) * 1.001 + 1e-5,
+            false => f32::INFINITY
+        };
+
+        let pixel = pixel_size(width, height, settings.units);
+        let _1w = pixel[0];
+        let _1h = pixel[1];
+
+        // Size of the distance space in the normalized space of the raster
+        let normalized = pixel_size(width, height, DistanceUnits::Normalized) / pixel;
+
+        let buffer_size = (width * height) as usize;
+        let mut image_buffer: Vec<f32> = vec![0.0; buffer_size];
+
+        // The lines are marked with the index of the last pixel that visited them, so the marks never need to be cleared
+        let visited = || vec![usize::MAX; lines.len()];
+        for_each_row_with(&mut image_buffer, width as usize, settings.threads, visited, |visited, y, row| {
+            let py = (y as f32 + 0.5) * _1h;
+            let scanline = scanline(py, lines, settings.fill_rule);
+
+            for (x, value) in row.iter_mut().enumerate() {
+                let px = (x as f32 + 0.5) * _1w;
+                let p = vec2(px, py);
+                let pixel_index = x + (y as usize * width as usize);
+
+                // Search area in the shape coordinates
+                let center = ((p * normalized) * scale) - offset;
+                let extent = scale * normalized * (cutoff * 1.001);
+
+                // Compute the distance between the nearby lines
+                let mut min_distance = f32::MAX;
+                for cell in self.grid.cells(center - extent, center + extent) {
+                    for &index in cell {
+                        if visited[index] == pixel_index {
+                            continue;
+                        }
+
+                        visited[index] = pixel_index;
+
+                        let (min, max) = bounds[index];
+                        if box_distance(min, max, p) > cutoff {
+                            continue;
+                        }
+
+                        let d = lines[index].distance(px, py);
+                        if d < min_distance {
+                            min_distance = d;
+                        }
+                    }
+                }
+
+                // Flip the distance if the pixel is inside the shape
+                let mut distance = -min_distance;
+                if scanline_scan(&scanline, px) {
+                    distance = -distance;
+                }
+
+                *value = sdf_value(distance, spread);
+            }
+        });
+
+        SdfRaster {
+            width,
+            height,
+            channels: 1,
+            buffer: image_buffer,
+            pixel_range: pixel_range(width, height, spread, units),
+        }
+    }
+
+}
+
+impl Grid {
+
+    fn new(lines: &[Line]) -> Grid {
+        let bounds: Vec<(Point, Point)> = lines.iter().map(|line| line.bounds()).collect();
+        let (min, max) = lines_bounds(lines).unwrap_or((vec2(f32::MAX, f32::MAX), vec2(f32::MIN, f32::MIN)));
+
+        // Aim for roughly one line per cell
+        let size = ((lines.len() as f32).sqrt().ceil() as usize).clamp(1, MAX_GRID_SIZE);
+        let axis = |min: f32, max: f32| {
+            let extent = max - min;
+            match extent > 0.0 && extent.is_finite() {
+                true => (size, extent / size as f32),
+                false => (1, 1.0)
+            }
+        };
+
+        let (columns, cell_width) = axis(min[0], max[0]);
+        let (rows, cell_height) = axis(min[1], max[1]);
+
+        let mut grid = Grid {
+            min,
+            cell_size: vec2(cell_width, cell_height),
+            columns,
+            rows,
+            offsets: vec![0; (columns * rows) + 1],
+            indices: Vec::new(),
+        };
+
+        // Count the lines in each cell, then fill the cells
+        for (line_min, line_max) in bounds.iter() {
+            let ([x0, x1], [y0, y1]) = grid.cell_range(*line_min, *line_max);
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    grid.offsets[x + (y * columns) + 1] += 1;
+                }
+            }
+        }
+
+        for i in 1..grid.offsets.len() {
+            grid.offsets[i] += grid.offsets[i-1];
+        }
+
+        let mut cursor = grid.offsets.clone();
+        grid.indices = vec![0; grid.offsets[columns * rows]];
+        for (index, (line_min, line_max)) in bounds.iter().enumerate() {
+            let ([x0, x1], [y0, y1]) = grid.cell_range(*line_min, *line_max);
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    let cell = x + (y * columns);
+                    grid.indices[cursor[cell]] = index;
+                    cursor[cell] += 1;
+                }
+            }
+        }
+
+        grid
+    }
+
+    /// Return the range of cells (inclusive) overlapping the box (`min`, `max`), clamped to the grid
+    fn cell_range(&self, min: Point, max: Point) -> ([usize; 2], [usize; 2]) {
+        let cell = |v: f32, origin: f32, size: f32, count: usize| {
+            let i = ((v - origin) / size).floor();
+            match i.is_nan() {
+                true => 0,
+                false => (i.max(0.0) as usize).min(count - 1)
+            }
+        };
+
+        let x0 = cell(min[0], self.min[0], self.cell_size[0], self.columns);
+        let x1 = cell(max[0], self.min[0], self.cell_size[0], self.columns);
+        let y0 = cell(min[1], self.min[1], self.cell_size[1], self.rows);
+        let y1 = cell(max[1], self.min[1], self.cell_size[1], self.rows);
+        ([x0, x1], [y0, y1])
+    }
+
+    /// Iterate over the cells overlapping the box (`min`, `max`)
+    fn cells(&self, min: Point, max: Point) -> impl Iterator<Item=&[usize]> {
+        let ([x0, x1], [y0, y1]) = self.cell_range(min, max);
+        (y0..=y1).flat_map(move |y| (x0..=x1).map(move |x| {
+            let cell = x + (y * self.columns);
+            &self.indices[self.offsets[cell]..self.offsets[cell+1]]
+        }))
+    }
+
+}
+
+/// Distance between `p` and the box (`min`, `max`). `0.0` if `p` is inside the box.
+fn box_distance(min: Point, max: Point, p: Point) -> f32 {
+    let dx = (min[0] - p[0]).max(p[0] - max[0]).max(0.0);
+    let dy = (min[1] - p[1]).max(p[1] - max[1]).max(0.0);
+    (dx*dx + dy*dy).sqrt()
+}
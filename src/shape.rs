@@ -0,0 +1,434 @@
+//! Shapes made of closed contours
+use std::ops::{Deref, Range};
+use crate::line::Line;
+use crate::math::{Point, Vec2, vec2};
+use crate::{FillRule, scanline, scanline_scan};
+
+/// A collection of contours. Each contour is an ordered sequence of lines where the end of a line is the start of the next one,
+/// and the end of the last line is the start of the first one.
+///
+/// The lines are stored one contour after the other, so a shape can be used anywhere a `&[Line]` is expected (ex: [sdf_generate](crate::sdf_generate)).
+///
+/// # Example
+///
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+///
+/// let mut shape = sdf::Shape::new();
+/// shape.move_to(0.5, 0.1)
+///     .line_to(0.9, 0.9)
+///     .quad_to(0.5, 0.6, 0.1, 0.9)
+///     .close();
+///
+/// assert_eq!(shape.contours().count(), 1);
+/// let sdf = sdf::sdf_generate(32, 32, 2, 6.0, &shape);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Shape {
+    lines: Vec<Line>,
+    /// Range of lines of each closed contour
+    contours: Vec<Range<usize>>,
+    start_point: Point,
+    previous_point: Point,
+}
+
+/// A contour of a [Shape]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Contour<'a> {
+    lines: &'a [Line],
+    closed: bool,
+}
+
+impl Shape {
+
+    /// Create an empty shape
+    pub fn new() -> Shape {
+        Shape::default()
+    }
+
+    /// Create a shape from a list of lines. The lines are split in contours everywhere the end of a line is not the start of the next one.
+    /// Contours that do not end where they start are closed with a straight line.
+    pub fn from_lines(lines: &[Line]) -> Shape {
+        let mut shape = Shape::new();
+        for contour in split_contours(lines) {
+            for &line in contour {
+                shape.push_joined(line);
+            }
+            shape.close();
+        }
+
+        shape
+    }
+
+    /// Start a new contour at (`x`, `y`). The current contour is closed first.
+    pub fn move_to(&mut self, x: f32, y: f32) -> &mut Self {
+        self.close();
+        self.start_point = vec2(x, y);
+        self.previous_point = self.start_point;
+        self
+    }
+
+    /// Add a straight line from the current point to (`x`, `y`)
+    pub fn line_to(&mut self, x: f32, y: f32) -> &mut Self {
+        let end = vec2(x, y);
+        self.lines.push(Line::Line { start: self.previous_point, end });
+        self.previous_point = end;
+        self
+    }
+
+    /// Add a quadratic curve from the current point to (`x`, `y`), with the control point (`x1`, `y1`)
+    pub fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) -> &mut Self {
+        let end = vec2(x, y);
+        self.lines.push(Line::Quad { start: self.previous_point, end, control: vec2(x1, y1) });
+        self.previous_point = end;
+        self
+    }
+
+    /// Add a cubic curve from the current point to (`x`, `y`), with the control points (`x1`, `y1`) and (`x2`, `y2`)
+    pub fn cubic_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) -> &mut Self {
+        let end = vec2(x, y);
+        self.lines.push(Line::Curve { start: self.previous_point, end, first_control: vec2(x1, y1), second_control: vec2(x2, y2) });
+        self.previous_point = end;
+        self
+    }
+
+    /// Add an elliptical arc from the current point to (`x`, `y`), with the same parameters as the SVG `A` command.
+    /// 
+    /// `radius` is the radius of the ellipse on its x and y axis, and `rotation` is the rotation of the x axis of the ellipse (in radians).
+    /// Out of the four possible arcs, `large_arc` selects an arc of more than 180 degrees, and `sweep` selects an arc going clockwise
+    /// (when `y` points down). The radius is scaled up if the ellipse is too small to reach (`x`, `y`).
+    /// A radius of zero adds a straight line, and an arc ending on the current point is ignored.
+    pub fn arc_to(&mut self, radius: Vec2, rotation: f32, large_arc: bool, sweep: bool, x: f32, y: f32) -> &mut Self {
+        let end = vec2(x, y);
+        if end == self.previous_point {
+            return self;
+        }
+
+        if radius[0] == 0.0 || radius[1] == 0.0 {
+            return self.line_to(x, y);
+        }
+
+        // Conversion from the endpoint to the center parameterization. See https://www.w3.org/TR/SVG11/implnote.html#ArcImplementationNotes
+        let (x1, y1) = (self.previous_point[0] as f64, self.previous_point[1] as f64);
+        let (x2, y2) = (x as f64, y as f64);
+        let (sin, cos) = (rotation as f64).sin_cos();
+        let (dx, dy) = ((x1 - x2) / 2.0, (y1 - y2) / 2.0);
+        let (x1p, y1p) = ((cos * dx) + (sin * dy), (cos * dy) - (sin * dx));
+
+        let (mut rx, mut ry) = ((radius[0] as f64).abs(), (radius[1] as f64).abs());
+        // The center is on the line between the end points if the radius is too small (or almost, as the square root
+        // below would turn the rounding errors of a half ellipse into a visible offset of the center)
+        let lambda = (x1p * x1p) / (rx * rx) + (y1p * y1p) / (ry * ry);
+        let on_chord = lambda >= 1.0 - 1e-6;
+        if on_chord {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        let numerator = (rx * rx * ry * ry) - (rx * rx * y1p * y1p) - (ry * ry * x1p * x1p);
+        let denominator = (rx * rx * y1p * y1p) + (ry * ry * x1p * x1p);
+        let mut coefficient = match on_chord {
+            true => 0.0,
+            false => (numerator / denominator).max(0.0).sqrt(),
+        };
+        if large_arc == sweep {
+            coefficient = -coefficient;
+        }
+
+        let (cxp, cyp) = (coefficient * rx * y1p / ry, -coefficient * ry * x1p / rx);
+        let center = vec2(
+            ((cos * cxp) - (sin * cyp) + (x1 + x2) / 2.0) as f32,
+            ((sin * cxp) + (cos * cyp) + (y1 + y2) / 2.0) as f32,
+        );
+
+        let start_angle = ((y1p - cyp) / ry).atan2((x1p - cxp) / rx);
+        let end_angle = ((-y1p - cyp) / ry).atan2((-x1p - cxp) / rx);
+        let mut sweep_angle = end_angle - start_angle;
+        if sweep && sweep_angle < 0.0 {
+            sweep_angle += std::f64::consts::TAU;
+        } else if !sweep && sweep_angle > 0.0 {
+            sweep_angle -= std::f64::consts::TAU;
+        }
+
+        self.push_joined(Line::Arc {
+            center,
+            radius: vec2(rx as f32, ry as f32),
+            rotation,
+            start_angle: start_angle as f32,
+            sweep_angle: sweep_angle as f32,
+        });
+
+        self
+    }
+
+    /// Close the current contour, with a straight line back to its first point if needed (the points are compared with a small tolerance). Does nothing if the contour is empty.
+    pub fn close(&mut self) -> &mut Self {
+        let first = self.contours.last().map(|contour| contour.end).unwrap_or(0);
+        if first == self.lines.len() {
+            return self;
+        }
+
+        if !connected(self.previous_point, self.start_point) {
+            self.lines.push(Line::Line { start: self.previous_point, end: self.start_point });
+        } else if self.previous_point != self.start_point {
+            let last = self.lines.len() - 1;
+            if set_start(&mut self.lines[first], self.previous_point) {
+                self.start_point = self.previous_point;
+            } else if !set_end(&mut self.lines[last], self.start_point) {
+                self.lines.push(Line::Line { start: self.previous_point, end: self.start_point });
+            }
+        }
+
+        self.previous_point = self.start_point;
+        self.contours.push(first..self.lines.len());
+        self
+    }
+
+    /// Add `line` to the current contour. The end points of arcs are computed with trigonometry and can be off by a rounding error,
+    /// so the joint with the previous line is moved to make them match exactly (the inside test counts a shared vertex only once
+    /// if both lines agree on it).
+    fn push_joined(&mut self, mut line: Line) {
+        let first = self.contours.last().map(|contour| contour.end).unwrap_or(0);
+        let start = line.start();
+        if first == self.lines.len() {
+            self.start_point = start;
+        } else if start != self.previous_point {
+            let last = self.lines.len() - 1;
+            if !set_end(&mut self.lines[last], start) && !set_start(&mut line, self.previous_point) {
+                self.lines.push(Line::Line { start: self.previous_point, end: start });
+            }
+        }
+
+        self.previous_point = line.end();
+        self.lines.push(line);
+    }
+
+    /// Iterate over the contours of the shape. The last contour is not closed if [Shape::close] was not called after its last line.
+    pub fn contours(&self) -> impl Iterator<Item=Contour<'_>> {
+        let first = self.contours.last().map(|contour| contour.end).unwrap_or(0);
+        let open = match first < self.lines.len() {
+            true => Some(Contour { lines: &self.lines[first..], closed: false }),
+            false => None,
+        };
+
+        self.contours.iter()
+            .map(move |range| Contour { lines: &self.lines[range.clone()], closed: true })
+            .chain(open)
+    }
+
+    /// Reverse the closed contours so that the outer contours have a positive [signed area](Contour::signed_area), and the holes a negative signed area.
+    /// 
+    /// A contour is a hole if it is inside an odd number of other contours. Once oriented, a shape without overlapping contours gives the same result
+    /// with the [EvenOdd](crate::FillRule::EvenOdd) and [NonZero](crate::FillRule::NonZero) fill rules. This is the orientation used by the fonts.
+    pub fn orient_contours(&mut self) {
+        for (index, range) in self.contours.iter().enumerate() {
+            let contour = &self.lines[range.clone()];
+            let area = signed_area(contour);
+            if area == 0.0 {
+                continue;
+            }
+
+            let p = contour[0].point(0.5);
+            let depth = self.contours.iter().enumerate()
+                .filter(|&(other, other_range)| other != index && contains(&self.lines[other_range.clone()], p))
+                .count();
+
+            let hole = depth % 2 == 1;
+            if (area < 0.0) != hole {
+                reverse_lines(&mut self.lines[range.clone()]);
+            }
+        }
+    }
+
+    /// Return the lines of every contour, one contour after the other
+    pub fn lines(&self) -> &[Line] {
+        &self.lines
+    }
+
+    /// Return the lines of every contour, one contour after the other
+    pub fn into_lines(self) -> Vec<Line> {
+        self.lines
+    }
+
+    /// Normalize the lines of the shape using the values provided. See [Line::normalize]
+    pub fn normalize(&mut self, width: f32, height: f32) {
+        self.normalize_with_offset(0.0, 0.0, width, height);
+    }
+
+    /// Normalize the lines of the shape using the values provided. See [Line::normalize_with_offset]
+    pub fn normalize_with_offset(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        for line in self.lines.iter_mut() {
+            line.normalize_with_offset(x, y, width, height);
+        }
+
+        let p = |p: Point| (p - vec2(x, y)) / vec2(width, height);
+        self.start_point = p(self.start_point);
+        self.previous_point = p(self.previous_point);
+    }
+
+}
+
+impl PartialEq for Shape {
+    /// Shapes are equal if they have the same contours. The current point of the builder is ignored.
+    fn eq(&self, other: &Shape) -> bool {
+        self.lines == other.lines && self.contours == other.contours
+    }
+}
+
+impl Deref for Shape {
+    type Target = [Line];
+
+    fn deref(&self) -> &[Line] {
+        &self.lines
+    }
+}
+
+impl AsRef<[Line]> for Shape {
+    fn as_ref(&self) -> &[Line] {
+        &self.lines
+    }
+}
+
+impl From<&[Line]> for Shape {
+    fn from(lines: &[Line]) -> Shape {
+        Shape::from_lines(lines)
+    }
+}
+
+impl From<Vec<Line>> for Shape {
+    fn from(lines: Vec<Line>) -> Shape {
+        Shape::from_lines(&lines)
+    }
+}
+
+impl From<Shape> for Vec<Line> {
+    fn from(shape: Shape) -> Vec<Line> {
+        shape.lines
+    }
+}
+
+impl<'a> Contour<'a> {
+
+    /// Return the lines of the contour
+    pub fn lines(&self) -> &'a [Line] {
+        self.lines
+    }
+
+    /// Check if the contour was closed. See [Shape::close]
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Return the signed area of the contour: positive if the contour goes clockwise when `y` points down (as in the normalized space of a raster),
+    /// and negative if it goes counterclockwise. See [Line::signed_area]
+    pub fn signed_area(&self) -> f32 {
+        signed_area(self.lines)
+    }
+
+    /// Return the winding of the contour: `1` if its signed area is positive, `-1` if it is negative and `0` if the contour has no area.
+    /// This is the value added to the winding number of the points inside the contour.
+    pub fn winding(&self) -> i32 {
+        let area = self.signed_area();
+        if area > 0.0 {
+            1
+        } else if area < 0.0 {
+            -1
+        } else {
+            0
+        }
+    }
+
+}
+
+impl Deref for Contour<'_> {
+    type Target = [Line];
+
+    fn deref(&self) -> &[Line] {
+        self.lines
+    }
+}
+
+/// Return the sum of the signed area of `lines`. If the lines form closed contours, this is the signed area of the shape. See [Line::signed_area]
+pub fn signed_area(lines: &[Line]) -> f32 {
+    lines.iter().map(|line| line.signed_area()).sum()
+}
+
+/// Return the exact bounding box (min, max) of `lines`, or `None` if there are no lines. See [Line::bounds]
+pub fn lines_bounds(lines: &[Line]) -> Option<(Point, Point)> {
+    lines.iter()
+        .map(|line| line.bounds())
+        .reduce(|(min, max), (line_min, line_max)| {
+            (vec2(min[0].min(line_min[0]), min[1].min(line_min[1])), vec2(max[0].max(line_max[0]), max[1].max(line_max[1])))
+        })
+}
+
+/// Flatten `lines` into polylines, with every point of the lines at most `tolerance` away from the polylines. See [Line::flatten]
+///
+/// A new polyline starts where a line does not start at the end of the previous line. The polyline of a closed contour ends on its first point.
+pub fn flatten_lines(lines: &[Line], tolerance: f32) -> Vec<Vec<Point>> {
+    let mut polylines: Vec<Vec<Point>> = Vec::new();
+    let mut previous: Option<Point> = None;
+    for line in lines {
+        if !previous.is_some_and(|p| connected(p, line.start())) {
+            polylines.push(vec![line.start()]);
+        }
+
+        if let Some(polyline) = polylines.last_mut() {
+            line.flatten(tolerance, polyline);
+        }
+
+        previous = Some(line.end());
+    }
+
+    polylines
+}
+
+/// Reverse the direction of a contour in place. The order of the lines is reversed, and each line is reversed with [Line::reverse]
+pub fn reverse_lines(lines: &mut [Line]) {
+    lines.reverse();
+    for line in lines.iter_mut() {
+        line.reverse();
+    }
+}
+
+/// Check if `p` is inside the closed contour `lines` (even-odd rule)
+fn contains(lines: &[Line], p: Point) -> bool {
+    scanline_scan(&scanline(p[1], lines, FillRule::EvenOdd), p[0])
+}
+
+/// Split `lines` into contours everywhere the end of a line is not the start of the next one (see [connected]).
+pub(crate) fn split_contours(lines: &[Line]) -> Vec<&[Line]> {
+    let mut contours = Vec::new();
+    let mut first = 0;
+    for i in 1..=lines.len() {
+        if i == lines.len() || !connected(lines[i-1].end(), lines[i].start()) {
+            contours.push(&lines[first..i]);
+            first = i;
+        }
+    }
+
+    contours
+}
+
+/// Move the start point of `line` to `point`. Returns `false` for arcs, as their end points are not stored.
+fn set_start(line: &mut Line, point: Point) -> bool {
+    match line {
+        Line::Line { start, .. } | Line::Quad { start, .. } | Line::Curve { start, .. } => { *start = point; true },
+        Line::Arc { .. } => false,
+    }
+}
+
+/// Move the end point of `line` to `point`. Returns `false` for arcs, as their end points are not stored.
+fn set_end(line: &mut Line, point: Point) -> bool {
+    match line {
+        Line::Line { end, .. } | Line::Quad { end, .. } | Line::Curve { end, .. } => { *end = point; true },
+        Line::Arc { .. } => false,
+    }
+}
+
+/// Check if the end point `a` of a line is the start point `b` of the next one.
+/// The end points of arcs are computed with trigonometry, so a small rounding error is accepted.
+pub(crate) fn connected(a: Point, b: Point) -> bool {
+    let scale = a[0].abs().max(a[1].abs()).max(1.0);
+    a == b || (a - b).length() <= 1e-5 * scale
+}
@@ -0,0 +1,209 @@
+//! Builders for common primitive shapes.
+//!
+//! All the shapes are made of closed contours with a positive [signed area](crate::Contour::signed_area) (see [Shape::orient_contours]).
+//! Coordinates and sizes are in the same space as the lines, so use normalized values to render the shapes directly with [sdf_generate](crate::sdf_generate).
+//! Angles are in radians and go clockwise from the x axis, as `y` points down.
+//!
+//! Curved parts are cubic curves, using one curve for each quarter of a circle. The curves are at most `0.03%` of the radius away from a true circle.
+//!
+//! # Example
+//!
+//! ```rust
+//! use easy_signed_distance_field as sdf;
+//! use sdf::shapes;
+//!
+//! let button = shapes::rounded_rectangle(sdf::vec2(0.1, 0.3), sdf::vec2(0.9, 0.7), 0.1);
+//! let sdf = sdf::sdf_generate(64, 64, 2, 6.0, &button);
+//! ```
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+use crate::math::{Point, Vec2, vec2};
+use crate::shape::{Shape, signed_area, reverse_lines};
+
+/// A circle at `center`
+pub fn circle(center: Point, radius: f32) -> Shape {
+    ellipse(center, vec2(radius, radius))
+}
+
+/// An ellipse at `center`, with the radius `radius[0]` on the x axis and `radius[1]` on the y axis
+pub fn ellipse(center: Point, radius: Vec2) -> Shape {
+    let start = center + vec2(radius[0], 0.0);
+    let mut shape = Shape::new();
+    shape.move_to(start[0], start[1]);
+    arc_to(&mut shape, center, radius, 0.0, TAU, start);
+    shape.close();
+    shape
+}
+
+/// A rectangle between `min` and `max`
+pub fn rectangle(min: Point, max: Point) -> Shape {
+    polygon(&[min, vec2(max[0], min[1]), max, vec2(min[0], max[1])])
+}
+
+/// A rectangle between `min` and `max` with rounded corners. `radius` is clamped to half of the smallest side of the rectangle.
+pub fn rounded_rectangle(min: Point, max: Point, radius: f32) -> Shape {
+    let size = max - min;
+    let r = radius.min(size[0].abs() / 2.0).min(size[1].abs() / 2.0).max(0.0);
+    if r == 0.0 {
+        return rectangle(min, max);
+    }
+
+    let (x0, y0) = (min[0].min(max[0]), min[1].min(max[1]));
+    let (x1, y1) = (min[0].max(max[0]), min[1].max(max[1]));
+    let radius = vec2(r, r);
+
+    // Each side, followed by the corner at its end
+    let sides = [
+        (vec2(x0 + r, y0), vec2(x1 - r, y0), vec2(x1 - r, y0 + r), -FRAC_PI_2),
+        (vec2(x1, y0 + r), vec2(x1, y1 - r), vec2(x1 - r, y1 - r), 0.0),
+        (vec2(x1 - r, y1), vec2(x0 + r, y1), vec2(x0 + r, y1 - r), FRAC_PI_2),
+        (vec2(x0, y1 - r), vec2(x0, y0 + r), vec2(x0 + r, y0 + r), PI),
+    ];
+
+    let mut shape = Shape::new();
+    shape.move_to(x0 + r, y0);
+    for (i, &(from, to, center, angle)) in sides.iter().enumerate() {
+        if from != to {
+            line_to(&mut shape, to);
+        }
+
+        let next = sides[(i + 1) % sides.len()].0;
+        arc_to(&mut shape, center, radius, angle, angle + FRAC_PI_2, next);
+    }
+
+    shape.close();
+    shape
+}
+
+/// A regular polygon with `sides` sides, inscribed in the circle at `center`. The first vertex is at the angle `rotation`.
+/// Returns an empty shape if `sides` is lower than 3.
+pub fn regular_polygon(center: Point, radius: f32, sides: u32, rotation: f32) -> Shape {
+    if sides < 3 {
+        return Shape::new();
+    }
+
+    let points: Vec<Point> = (0..sides)
+        .map(|i| polar(center, radius, rotation + (TAU * i as f32 / sides as f32)))
+        .collect();
+
+    polygon(&points)
+}
+
+/// A star with `points` branches. The tips are on the circle of radius `outer_radius`, and the inner vertices on the circle of radius `inner_radius`.
+/// The first tip is at the angle `rotation`. Returns an empty shape if `points` is lower than 2.
+pub fn star(center: Point, outer_radius: f32, inner_radius: f32, points: u32, rotation: f32) -> Shape {
+    if points < 2 {
+        return Shape::new();
+    }
+
+    let step = PI / points as f32;
+    let vertices: Vec<Point> = (0..points * 2)
+        .map(|i| {
+            let radius = match i % 2 == 0 {
+                true => outer_radius,
+                false => inner_radius
+            };
+
+            polar(center, radius, rotation + (step * i as f32))
+        })
+        .collect();
+
+    polygon(&vertices)
+}
+
+/// A capsule (a segment with round ends) from `start` to `end`. The points of the shape are at `radius` from the segment.
+pub fn capsule(start: Point, end: Point, radius: f32) -> Shape {
+    let direction = end - start;
+    if direction == vec2(0.0, 0.0) {
+        return circle(start, radius);
+    }
+
+    let angle = direction[1].atan2(direction[0]);
+    let (a0, a1, a2) = (angle - FRAC_PI_2, angle + FRAC_PI_2, angle + PI + FRAC_PI_2);
+    let first = polar(end, radius, a0);
+    let second = polar(end, radius, a1);
+    let third = polar(start, radius, a1);
+    let fourth = polar(start, radius, a2);
+
+    let mut shape = Shape::new();
+    shape.move_to(first[0], first[1]);
+    arc_to(&mut shape, end, vec2(radius, radius), a0, a1, second);
+    line_to(&mut shape, third);
+    arc_to(&mut shape, start, vec2(radius, radius), a1, a2, fourth);
+    shape.close();
+    shape
+}
+
+/// An arrow going from `start` to `end`. The shaft is `shaft_width` wide, and the head is `head_width` wide and `head_length` long.
+/// `head_length` is clamped to the length of the arrow.
+pub fn arrow(start: Point, end: Point, shaft_width: f32, head_width: f32, head_length: f32) -> Shape {
+    let length = (end - start).length();
+    if length == 0.0 {
+        return Shape::new();
+    }
+
+    let direction = (end - start) / length;
+    let normal = vec2(-direction[1], direction[0]);
+    let head = end - direction * head_length.clamp(0.0, length);
+    let (shaft, wing) = (normal * (shaft_width / 2.0), normal * (head_width / 2.0));
+
+    polygon(&[
+        start + shaft,
+        head + shaft,
+        head + wing,
+        end,
+        head - wing,
+        head - shaft,
+        start - shaft,
+    ])
+}
+
+/// A closed polygon going through `points`, with a positive signed area
+fn polygon(points: &[Point]) -> Shape {
+    let mut shape = Shape::new();
+    shape.move_to(points[0][0], points[0][1]);
+    for &p in &points[1..] {
+        line_to(&mut shape, p);
+    }
+
+    shape.close();
+
+    if signed_area(&shape) < 0.0 {
+        let mut lines = shape.into_lines();
+        reverse_lines(&mut lines);
+        shape = Shape::from_lines(&lines);
+    }
+
+    shape
+}
+
+fn polar(center: Point, radius: f32, angle: f32) -> Point {
+    center + vec2(angle.cos(), angle.sin()) * radius
+}
+
+fn line_to(shape: &mut Shape, p: Point) {
+    shape.line_to(p[0], p[1]);
+}
+
+/// Add an elliptic arc from the angle `from` to the angle `to` to the shape, with one cubic curve for each quarter of the ellipse (or less).
+/// The arc must start at the current point of the shape, and `end` is the last point of the arc.
+fn arc_to(shape: &mut Shape, center: Point, radius: Vec2, from: f32, to: f32, end: Point) {
+    // The small margin avoids an extra curve when the angle is a multiple of a quarter with rounding errors
+    let count = (((to - from).abs() / FRAC_PI_2) - 1e-4).ceil().max(1.0) as u32;
+    let step = (to - from) / count as f32;
+    let k = (4.0 / 3.0) * (step / 4.0).tan();
+    let point = |angle: f32| center + vec2(angle.cos(), angle.sin()) * radius;
+    let tangent = |angle: f32| vec2(-angle.sin(), angle.cos()) * radius * k;
+
+    for i in 0..count {
+        let a0 = from + step * i as f32;
+        let a1 = from + step * (i + 1) as f32;
+        let (p0, p1) = (point(a0), point(a1));
+        let (c1, c2) = (p0 + tangent(a0), p1 - tangent(a1));
+        let p1 = match i + 1 == count {
+            true => end,
+            false => p1,
+        };
+
+        shape.cubic_to(c1[0], c1[1], c2[0], c2[1], p1[0], p1[1]);
+    }
+}
@@ -0,0 +1,507 @@
+//! Signed distance field of the outline of a path, with a stroke width, joins, caps and dashes.
+//!
+//! The stroke is built from pieces: the body of each line (the points at less than half the stroke width from the line),
+//! cut by a straight edge at the ends with a butt or square cap and at the miter or bevel joins, and a polygon filling the outer
+//! side of each miter or bevel join. Outside of the stroke, the signed distance is the largest signed distance of the pieces.
+//! Inside, it is the largest of this value and of the distance to the nearest edge of a piece that is not between two pieces of a join.
+//! With round joins and caps, the signed distance is exactly half the stroke width minus the distance to the path.
+use crate::line::Line;
+use crate::math::{Point, Vec2, vec2};
+use crate::shape::{connected, split_contours};
+use crate::{SdfRaster, SdfSettings, Spread, distance_space_lines, for_each_row, pad_lines, pixel_range, pixel_size, sdf_value};
+
+/// Number of samples used to measure the length of a line when splitting it in dashes
+const LENGTH_SAMPLES: usize = 32;
+
+/// Shape of the corners between two lines of a stroke. See [Stroke]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineJoin {
+    /// Sharp corner. The value is the miter limit: the largest ratio between the length of the corner (from the inner corner
+    /// to the tip) and the stroke width. Sharper corners are drawn as [LineJoin::Bevel]. `4.0` is the SVG default.
+    Miter(f32),
+    /// Round corner, centered on the point between the lines
+    Round,
+    /// Corner cut by a straight line
+    Bevel,
+}
+
+/// Shape of the ends of an open path and of the dashes of a stroke. See [Stroke]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// The stroke ends exactly at the end of the path
+    #[default]
+    Butt,
+    /// Half circle centered on the end of the path
+    Round,
+    /// The stroke goes past the end of the path by half the stroke width
+    Square,
+}
+
+impl Default for LineJoin {
+    fn default() -> Self {
+        LineJoin::Miter(4.0)
+    }
+}
+
+/// Style of a stroke rendered by [sdf_stroke]
+///
+/// Distances (`width`, `dashes` and `dash_offset`) are in the units of the distances of the sdf: the normalized space of the raster
+/// with the default settings, or pixels with [DistanceUnits::Pixels](crate::DistanceUnits::Pixels) or a [Spread::Pixels] spread.
+///
+/// # Example
+///
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+///
+/// let dotted = sdf::Stroke { cap: sdf::LineCap::Round, dashes: vec![0.0, 0.1], ..sdf::Stroke::new(0.04) };
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stroke {
+    /// Width of the stroke. The path is in the middle of the stroke.
+    pub width: f32,
+    /// Shape of the corners between two lines. The default is `LineJoin::Miter(4.0)`.
+    pub join: LineJoin,
+    /// Shape of the ends of the open paths and of the dashes. The default is [LineCap::Butt].
+    pub cap: LineCap,
+    /// Length of the dashes and of the gaps between them, alternating: `[dash, gap, dash, gap, ...]`. A list with an odd number
+    /// of values is repeated twice. An empty list, or a list with negative values or only zeros, draws a solid stroke.
+    /// Dashes of zero length draw a dot with round or square caps.
+    pub dashes: Vec<f32>,
+    /// Distance in the dash pattern where each contour starts
+    pub dash_offset: f32,
+}
+
+impl Stroke {
+    /// A solid stroke of `width`, with the SVG defaults: miter joins with a limit of 4 and butt caps.
+    pub fn new(width: f32) -> Stroke {
+        Stroke {
+            width,
+            join: LineJoin::default(),
+            cap: LineCap::default(),
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+}
+
+/// Rasterize the stroke of the path defined by `lines`
+///
+/// Unlike [sdf_generate](crate::sdf_generate), the lines do not need to be closed. The lines are split in contours everywhere the end of a line
+/// is not the start of the next one. Contours that end where they start are closed and get a join instead of caps.
+///
+/// # Arguments
+///
+/// * `width`, `height`, `padding`, `spread`: Same as [sdf_generate](crate::sdf_generate)
+/// * `lines`: List of [Line] to be stroked. Assumes the lines coordinates are normalized.
+/// * `stroke`: Width and style of the stroke
+///
+/// # Note
+///
+/// * Outside of the stroke, the values are exact. Inside the stroke, the distances can be lower than the true distance to the edge near
+///   miter and bevel joins, and where the path overlaps itself.
+///
+/// # Example
+///
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+///
+/// let polyline = [
+///     sdf::Line::Line { start: sdf::vec2(0.1, 0.8), end: sdf::vec2(0.4, 0.3) },
+///     sdf::Line::Line { start: sdf::vec2(0.4, 0.3), end: sdf::vec2(0.6, 0.6) },
+///     sdf::Line::Line { start: sdf::vec2(0.6, 0.6), end: sdf::vec2(0.9, 0.2) },
+/// ];
+///
+/// let stroke = sdf::Stroke { join: sdf::LineJoin::Round, cap: sdf::LineCap::Round, ..sdf::Stroke::new(0.05) };
+/// let sdf = sdf::sdf_stroke(64, 64, 2, 8.0, &polyline, &stroke);
+/// ```
+pub fn sdf_stroke(
+    width: u32,
+    height: u32,
+    padding: i32,
+    spread: impl Into<Spread>,
+    lines: &[Line],
+    stroke: &Stroke,
+) -> SdfRaster {
+    sdf_stroke_with_settings(width, height, padding, spread, lines, stroke, SdfSettings::default())
+}
+
+/// Same as [sdf_stroke], but with extra [SdfSettings] to control the generation. `settings.fill_rule` is not used.
+pub fn sdf_stroke_with_settings(
+    width: u32,
+    height: u32,
+    padding: i32,
+    spread: impl Into<Spread>,
+    lines: &[Line],
+    stroke: &Stroke,
+    settings: SdfSettings,
+) -> SdfRaster {
+    let (spread, units) = spread.into().resolve(settings.units);
+    let padded_lines = distance_space_lines(width, height, units, pad_lines(width, height, padding, lines));
+    let pieces = stroke_pieces(&padded_lines, stroke);
+    let half_width = stroke.width * 0.5;
+
+    let mut buffer: Vec<f32> = vec![0.0; (width * height) as usize];
+    let pixel = pixel_size(width, height, units);
+    for_each_row(&mut buffer, width as usize, settings.threads, |y, row| {
+        let py = (y as f32 + 0.5) * pixel[1];
+        for (x, value) in row.iter_mut().enumerate() {
+            let p = vec2((x as f32 + 0.5) * pixel[0], py);
+            let (distance, edge_distance) = pieces.iter()
+                .map(|piece| piece.distances(p, half_width))
+                .fold((f32::MIN, f32::MAX), |(d0, e0), (d1, e1)| (d0.max(d1), e0.min(e1)));
+
+            let distance = match distance > 0.0 {
+                true => distance.max(edge_distance),
+                false => distance,
+            };
+
+            *value = sdf_value(distance, spread);
+        }
+    });
+
+    SdfRaster {
+        width,
+        height,
+        channels: 1,
+        buffer,
+        pixel_range: pixel_range(width, height, spread, units),
+    }
+}
+
+/// A straight edge cutting the end of the body of a line
+#[derive(Copy, Clone, Debug)]
+struct Cut {
+    /// End point of the line
+    point: Point,
+    /// Unit vector pointing away from the line
+    direction: Vec2,
+    /// Distance between the end point and the edge
+    extension: f32,
+    /// `false` if the edge is between the line and a join, so it is not an edge of the stroke
+    edge: bool,
+}
+
+/// A part of a stroke, with its own signed distance
+#[derive(Clone, Debug)]
+enum Piece {
+    /// The points near a line. Ends without a cut are round.
+    Body { line: Line, start: Option<Cut>, end: Option<Cut> },
+    /// A convex polygon filling a join. The first point is the point between the lines, and its two edges are against the lines.
+    Join(Vec<Point>),
+    /// A dash of zero length. `direction` is `None` for a round dot.
+    Dot { center: Point, direction: Option<Vec2> },
+}
+
+impl Piece {
+
+    /// Return the signed distance of `p` to the piece (positive inside), and the distance of `p` to the edges of the piece that are edges of the stroke.
+    /// Both are exact or lower than the true distances.
+    fn distances(&self, p: Point, half_width: f32) -> (f32, f32) {
+        match self {
+            Piece::Body { line, start, end } => {
+                let (t, distance) = line.closest(p);
+                let mut sd = half_width - distance;
+                let mut edge = sd.abs();
+                for (cut, end_t) in [(start, 0.0), (end, 1.0)] {
+                    let Some(cut) = cut else { continue };
+                    let along = (p - cut.point).dot(cut.direction) - cut.extension;
+                    match t == end_t {
+                        // Near the end, the body is a rectangle
+                        true => {
+                            let across = (p - cut.point).cross(cut.direction).abs() - half_width;
+                            sd = -box_distance(along, across);
+                            edge = match cut.edge {
+                                true => sd.abs(),
+                                false => vec2(along.max(0.0), across).length(),
+                            };
+                        },
+                        false => {
+                            sd = sd.min(-along);
+                            if cut.edge {
+                                edge = edge.min(along.abs());
+                            }
+                        }
+                    }
+                }
+
+                (sd, edge)
+            },
+            Piece::Join(points) => join_distances(points, p),
+            Piece::Dot { center, direction: None } => {
+                let sd = half_width - (p - *center).length();
+                (sd, sd.abs())
+            },
+            Piece::Dot { center, direction: Some(direction) } => {
+                let offset = p - *center;
+                let along = offset.dot(*direction).abs() - half_width;
+                let across = offset.cross(*direction).abs() - half_width;
+                let sd = -box_distance(along, across);
+                (sd, sd.abs())
+            },
+        }
+    }
+
+}
+
+/// Signed distance of a box corner, negative inside. `x` and `y` are the signed distances to the sides of the corner.
+fn box_distance(x: f32, y: f32) -> f32 {
+    vec2(x.max(0.0), y.max(0.0)).length() + x.max(y).min(0.0)
+}
+
+/// Return the signed distance to the polygon of a join (positive inside), and the distance to its edges, without the two edges against the lines.
+/// Credits to https://iquilezles.org/articles/distfunctions2d/
+fn join_distances(points: &[Point], p: Point) -> (f32, f32) {
+    let (mut distance, mut outer_distance) = (f32::MAX, f32::MAX);
+    let mut inside = false;
+    for i in 0..points.len() {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        let (e, w) = (b - a, p - a);
+        let h = match e.dot(e) > 0.0 {
+            true => (w.dot(e) / e.dot(e)).clamp(0.0, 1.0),
+            false => 0.0,
+        };
+
+        let q = w - e * h;
+        distance = distance.min(q.dot(q));
+        if i > 0 && i + 1 < points.len() {
+            outer_distance = outer_distance.min(q.dot(q));
+        }
+
+        if (a[1] > p[1]) != (b[1] > p[1]) && p[0] < a[0] + (p[1] - a[1]) * e[0] / e[1] {
+            inside = !inside;
+        }
+    }
+
+    match inside {
+        true => (distance.sqrt(), outer_distance.sqrt()),
+        false => (-distance.sqrt(), outer_distance.sqrt())
+    }
+}
+
+/// A list of connected lines stroked with joins between the lines, and caps at the ends if the path is not closed
+struct Path {
+    lines: Vec<Line>,
+    closed: bool,
+}
+
+/// Split the stroke of `lines` in pieces
+fn stroke_pieces(lines: &[Line], stroke: &Stroke) -> Vec<Piece> {
+    let half_width = stroke.width * 0.5;
+    let mut pieces = Vec::new();
+    let mut dots = Vec::new();
+    let mut paths = Vec::new();
+
+    for contour in split_contours(lines) {
+        let closed = connected(contour[contour.len() - 1].end(), contour[0].start());
+        match dash_pattern(&stroke.dashes) {
+            Some(pattern) => dash_contour(contour, closed, &pattern, stroke.dash_offset, &mut paths, &mut dots),
+            None => paths.push(Path { lines: contour.to_vec(), closed }),
+        }
+    }
+
+    let cap = |point: Point, direction: Vec2| match stroke.cap {
+        LineCap::Round => None,
+        LineCap::Butt => Some(Cut { point, direction, extension: 0.0, edge: true }),
+        LineCap::Square => Some(Cut { point, direction, extension: half_width, edge: true }),
+    };
+
+    let join_cut = |point: Point, direction: Vec2| match stroke.join {
+        LineJoin::Round => None,
+        _ => Some(Cut { point, direction, extension: 0.0, edge: false }),
+    };
+
+    for path in paths.iter() {
+        let count = path.lines.len();
+        for (i, line) in path.lines.iter().enumerate() {
+            let start_direction = unit(line.direction(0.0)) * -1.0;
+            let end_direction = unit(line.direction(1.0));
+            let start = match i > 0 || path.closed {
+                true => join_cut(line.start(), start_direction),
+                false => cap(line.start(), start_direction),
+            };
+            let end = match i + 1 < count || path.closed {
+                true => join_cut(line.end(), end_direction),
+                false => cap(line.end(), end_direction),
+            };
+
+            pieces.push(Piece::Body { line: *line, start, end });
+
+            if i + 1 < count || path.closed {
+                let next = &path.lines[(i + 1) % count];
+                if let Some(join) = join_polygon(line.end(), end_direction, unit(next.direction(0.0)), half_width, stroke.join) {
+                    pieces.push(join);
+                }
+            }
+        }
+    }
+
+    for (center, direction) in dots {
+        match stroke.cap {
+            LineCap::Butt => {},
+            LineCap::Round => pieces.push(Piece::Dot { center, direction: None }),
+            LineCap::Square => pieces.push(Piece::Dot { center, direction: Some(unit(direction)) }),
+        }
+    }
+
+    pieces
+}
+
+/// Return the polygon filling the outer side of the join at `point`, between a line ending in the direction `incoming` and a line starting
+/// in the direction `outgoing`. Round joins and lines going in the same direction do not need a polygon.
+fn join_polygon(point: Point, incoming: Vec2, outgoing: Vec2, half_width: f32, join: LineJoin) -> Option<Piece> {
+    let turn = incoming.cross(outgoing);
+    if join == LineJoin::Round || turn == 0.0 {
+        return None;
+    }
+
+    // Normals on the outer side of the corner
+    let side = -turn.signum();
+    let a = vec2(-incoming[1], incoming[0]) * side;
+    let b = vec2(-outgoing[1], outgoing[0]) * side;
+    let (a_point, b_point) = (point + a * half_width, point + b * half_width);
+
+    if let LineJoin::Miter(limit) = join {
+        // `1 / cos` of half the angle between the normals is the ratio between the miter length and the stroke width
+        let miter = unit(a + b);
+        let cos = miter.dot(a);
+        if cos > 0.0 && 1.0 / cos <= limit {
+            let tip = point + miter * (half_width / cos);
+            return Some(Piece::Join(vec![point, a_point, tip, b_point]));
+        }
+    }
+
+    Some(Piece::Join(vec![point, a_point, b_point]))
+}
+
+/// Return the dash pattern with an even number of values, or `None` if the stroke is solid
+fn dash_pattern(dashes: &[f32]) -> Option<Vec<f32>> {
+    let total: f32 = dashes.iter().sum();
+    if dashes.is_empty() || dashes.iter().any(|&d| d < 0.0 || !d.is_finite()) || total <= 0.0 {
+        return None;
+    }
+
+    let mut pattern = dashes.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_slice(dashes);
+    }
+
+    Some(pattern)
+}
+
+/// Split a contour in dashes. The dashes are added to `paths`, and the dashes of zero length are added to `dots` with the direction of the line.
+/// If the contour is `closed` and a dash goes through its first point, the end and the start of the dash are a single path with a join.
+fn dash_contour(contour: &[Line], closed: bool, pattern: &[f32], offset: f32, paths: &mut Vec<Path>, dots: &mut Vec<(Point, Vec2)>) {
+    let total: f32 = pattern.iter().sum();
+
+    // Move to `offset` in the pattern
+    let mut index = 0;
+    let mut remaining = offset.rem_euclid(total);
+    while remaining > pattern[index] {
+        remaining -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+    remaining = pattern[index] - remaining;
+
+    let first = paths.len();
+    let starts_on = index % 2 == 0 && remaining > 0.0;
+    let mut dash: Vec<Line> = Vec::new();
+    for line in contour {
+        let lengths = length_table(line);
+        let length = lengths[LENGTH_SAMPLES];
+        let mut position = 0.0;
+        loop {
+            let on = index % 2 == 0;
+            let step = remaining.min(length - position);
+            if on && step > 0.0 {
+                let t0 = parameter_at(&lengths, position);
+                let t1 = parameter_at(&lengths, position + step);
+                dash.push(sub_line(line, t0, t1));
+            }
+
+            position += step;
+            remaining -= step;
+            if remaining > 0.0 {
+                break;
+            }
+
+            // End of a dash or a gap
+            if on && !dash.is_empty() {
+                paths.push(Path { lines: std::mem::take(&mut dash), closed: false });
+            } else if on && pattern[index] == 0.0 {
+                let t = parameter_at(&lengths, position);
+                dots.push((line.point(t), line.direction(t)));
+            }
+
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+    }
+
+    if dash.is_empty() {
+        return;
+    }
+
+    match closed && starts_on {
+        // The last dash continues with the first dash of the contour
+        true if paths.len() > first => {
+            dash.append(&mut paths[first].lines);
+            paths[first].lines = dash;
+        },
+        // A single dash covers the whole contour
+        true => paths.push(Path { lines: dash, closed: true }),
+        false => paths.push(Path { lines: dash, closed: false }),
+    }
+}
+
+/// Return the length of the line from its start to the parameters `0/LENGTH_SAMPLES`, `1/LENGTH_SAMPLES`, ..., `1`.
+/// The length of each interval is integrated with a 3 points Gauss-Legendre quadrature.
+fn length_table(line: &Line) -> [f32; LENGTH_SAMPLES + 1] {
+    const NODES: [(f32, f32); 3] = [(0.11270166, 0.2777778), (0.5, 0.44444445), (0.8872983, 0.2777778)];
+    let step = 1.0 / LENGTH_SAMPLES as f32;
+    let mut lengths = [0.0; LENGTH_SAMPLES + 1];
+    for i in 1..=LENGTH_SAMPLES {
+        let t0 = (i - 1) as f32 * step;
+        let length: f32 = NODES.iter().map(|&(x, w)| line.direction(t0 + x * step).length() * w).sum();
+        lengths[i] = lengths[i - 1] + length * step;
+    }
+
+    lengths
+}
+
+/// Return the parameter of the point at `length` from the start of a line. See [length_table]
+fn parameter_at(lengths: &[f32; LENGTH_SAMPLES + 1], length: f32) -> f32 {
+    if length >= lengths[LENGTH_SAMPLES] {
+        return 1.0;
+    }
+
+    let i = lengths.partition_point(|&l| l <= length).clamp(1, LENGTH_SAMPLES);
+    let (l0, l1) = (lengths[i - 1], lengths[i]);
+    let f = match l1 > l0 {
+        true => (length - l0) / (l1 - l0),
+        false => 0.0,
+    };
+
+    ((i - 1) as f32 + f) / LENGTH_SAMPLES as f32
+}
+
+/// Return the part of `line` between the parameters `t0` and `t1`
+fn sub_line(line: &Line, t0: f32, t1: f32) -> Line {
+    let line = match t1 < 1.0 {
+        true => line.split(t1).0,
+        false => *line,
+    };
+
+    match t0 > 0.0 && t1 > 0.0 {
+        true => line.split(t0 / t1).1,
+        false => line,
+    }
+}
+
+/// Return `v` with a length of 1, or zero if `v` is zero
+fn unit(v: Vec2) -> Vec2 {
+    let length = v.length();
+    match length > 0.0 {
+        true => v / length,
+        false => vec2(0.0, 0.0),
+    }
+}
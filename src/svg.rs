@@ -0,0 +1,632 @@
+//! Reader for a subset of SVG documents, to render icons and other simple vector images.
+//!
+//! The supported elements are `<path>`, `<rect>`, `<circle>`, `<ellipse>`, `<polygon>`, `<polyline>` and the groups (`<g>`, `<a>` and nested `<svg>`).
+//! The `transform` attributes are applied, and the `viewBox` of the root element (or its `width` and `height`) is mapped to the normalized space of the sdf.
+//! The `viewBox` of a nested `<svg>` is mapped to its `x`, `y`, `width` and `height` following its `preserveAspectRatio`, but its content is not clipped.
+//!
+//! Only the filled geometry is read. Strokes, text, `<use>` and CSS stylesheets are ignored, as is the content of the elements that are not
+//! rendered directly (`<defs>`, `<clipPath>`, `<mask>`, etc). Elements with `fill="none"` or `display="none"`, as attributes or in a `style`
+//! attribute, are skipped.
+use std::{fmt, f32::consts::TAU};
+use crate::{FillRule, SdfRaster, SdfSettings, Spread, sdf_generate_with_settings};
+use crate::line::Line;
+use crate::math::vec2;
+use crate::path::{Parser, PathError, ViewBox, parse_svg_path};
+use crate::shape::{Shape, reverse_lines, signed_area};
+
+/// Affine transform `[a, b, c, d, e, f]`, as in the `matrix` function of a `transform` attribute
+type Matrix = [f32; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// Error returned when reading an invalid SVG document. `position` is the byte offset of the error in the document.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SvgError {
+    /// The document is not well formed, or its root element is not `<svg>`
+    InvalidDocument { position: usize },
+    /// The value of an attribute is not valid. `position` is the start of the value
+    InvalidAttribute { position: usize },
+    /// The data of a `<path>` is not valid. `position` is the start of the `d` attribute, and the position of `error` is relative to it
+    InvalidPath { position: usize, error: PathError },
+    /// The root element has no `viewBox`, nor a `width` and a `height`
+    MissingViewBox,
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            SvgError::InvalidDocument { position } => write!(f, "Invalid document at {}", position),
+            SvgError::InvalidAttribute { position } => write!(f, "Invalid attribute value at {}", position),
+            SvgError::InvalidPath { position, error } => write!(f, "Invalid path data at {}: {}", position, error),
+            SvgError::MissingViewBox => write!(f, "The svg element has no viewBox, width or height"),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SvgError::InvalidPath { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Read a SVG document into a list of lines, in the normalized space of the sdf. See [ViewBox::normalize]
+///
+/// The contours of each element are oriented so that the lines render like the document with [FillRule::NonZero], as [sdf_from_svg] does.
+/// The default [FillRule::EvenOdd] gives the same result unless some elements overlap.
+///
+/// # Example
+///
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+///
+/// let svg = r#"<svg viewBox="0 0 24 24"><rect x="4" y="4" width="16" height="16" rx="2"/></svg>"#;
+/// let lines = sdf::parse_svg(svg).unwrap();
+/// assert_eq!(lines.len(), 8);
+/// ```
+pub fn parse_svg(svg: &str) -> Result<Vec<Line>, SvgError> {
+    let mut reader = Reader { data: svg, position: 0 };
+    let mut stack: Vec<Group> = Vec::new();
+    let mut view_box = None;
+    let mut lines = Vec::new();
+
+    while let Some(tag) = reader.next_tag()? {
+        if tag.kind == TagKind::End {
+            match stack.pop() {
+                Some(group) if group.name == tag.name => continue,
+                _ => return Err(SvgError::InvalidDocument { position: tag.position }),
+            }
+        }
+
+        let parent = match stack.last() {
+            Some(parent) => *parent,
+            None if view_box.is_none() && tag.name == "svg" => {
+                let root = root_view_box(&tag)?;
+                view_box = Some(root);
+                Group { name: "svg", transform: IDENTITY, viewport: (root.width, root.height), rendered: true, fill: true, even_odd: false }
+            },
+            None => return Err(SvgError::InvalidDocument { position: tag.position }),
+        };
+
+        let mut group = parent.child(&tag)?;
+        if tag.name == "svg" && !stack.is_empty() {
+            group = group.nested_viewport(&tag)?;
+        }
+
+        if group.rendered && group.fill {
+            let element = element_lines(&tag)?.iter()
+                .map(|line| line.transform(group.transform))
+                .collect();
+
+            lines.extend(orient(element, group.even_odd));
+        }
+
+        if tag.kind == TagKind::Start {
+            stack.push(group);
+        }
+    }
+
+    match (view_box, stack.is_empty()) {
+        (Some(view_box), true) => {
+            view_box.normalize(&mut lines);
+            Ok(lines)
+        },
+        _ => Err(SvgError::InvalidDocument { position: svg.len() }),
+    }
+}
+
+/// Render a SVG document as a sdf, with the view box of the document filling the raster (without the padding). See [parse_svg]
+///
+/// # Example
+///
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+///
+/// let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24">
+///     <g transform="rotate(45 12 12)">
+///         <rect x="6" y="6" width="12" height="12"/>
+///     </g>
+///     <circle cx="12" cy="12" r="4"/>
+/// </svg>"#;
+///
+/// let sdf = sdf::sdf_from_svg(svg, 64, 64, 2, 8.0).unwrap();
+/// ```
+pub fn sdf_from_svg(svg: &str, width: u32, height: u32, padding: i32, spread: impl Into<Spread>) -> Result<SdfRaster, SvgError> {
+    let lines = parse_svg(svg)?;
+    let settings = SdfSettings { fill_rule: FillRule::NonZero, ..Default::default() };
+    Ok(sdf_generate_with_settings(width, height, padding, spread, &lines, settings))
+}
+
+/// The view box of the root element, or its size if it has no `viewBox`
+fn root_view_box(tag: &Tag) -> Result<ViewBox, SvgError> {
+    if let Some((value, position)) = tag.attribute("viewBox") {
+        return ViewBox::parse(value).ok_or(SvgError::InvalidAttribute { position });
+    }
+
+    match (tag.length("width")?, tag.length("height")?) {
+        (Some(width), Some(height)) if width > 0.0 && height > 0.0 => Ok(ViewBox { x: 0.0, y: 0.0, width, height }),
+        _ => Err(SvgError::MissingViewBox),
+    }
+}
+
+/// The lines of an element, in its own user space. Elements without geometry return no lines.
+fn element_lines(tag: &Tag) -> Result<Vec<Line>, SvgError> {
+    match tag.name {
+        "path" => match tag.attribute("d") {
+            Some((d, position)) => parse_svg_path(d).map_err(|error| SvgError::InvalidPath { position, error }),
+            None => Ok(Vec::new()),
+        },
+        "rect" => rect(tag),
+        "circle" => {
+            let r = tag.length("r")?.unwrap_or(0.0);
+            ellipse(tag, r, r)
+        },
+        "ellipse" => {
+            let (rx, ry) = (tag.length("rx")?.unwrap_or(0.0), tag.length("ry")?.unwrap_or(0.0));
+            ellipse(tag, rx, ry)
+        },
+        "polygon" | "polyline" => polygon(tag),
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn rect(tag: &Tag) -> Result<Vec<Line>, SvgError> {
+    let (x, y) = (tag.length("x")?.unwrap_or(0.0), tag.length("y")?.unwrap_or(0.0));
+    let (width, height) = (tag.length("width")?.unwrap_or(0.0), tag.length("height")?.unwrap_or(0.0));
+    if !(width > 0.0 && height > 0.0) {
+        return Ok(Vec::new());
+    }
+
+    // A missing radius is the same as the other one
+    let (rx, ry) = match (tag.length("rx")?, tag.length("ry")?) {
+        (Some(rx), Some(ry)) => (rx, ry),
+        (Some(r), None) | (None, Some(r)) => (r, r),
+        (None, None) => (0.0, 0.0),
+    };
+
+    let (rx, ry) = (rx.clamp(0.0, width / 2.0), ry.clamp(0.0, height / 2.0));
+    let (x1, y1) = (x + width, y + height);
+    let mut shape = Shape::new();
+    if rx == 0.0 || ry == 0.0 {
+        shape.move_to(x, y).line_to(x1, y).line_to(x1, y1).line_to(x, y1).close();
+        return Ok(shape.into_lines());
+    }
+
+    // Each side, followed by the corner at its end. A side is skipped if its corners are touching.
+    let sides = [
+        (vec2(x1 - rx, y), vec2(x1, y + ry)),
+        (vec2(x1, y1 - ry), vec2(x1 - rx, y1)),
+        (vec2(x + rx, y1), vec2(x, y1 - ry)),
+        (vec2(x, y + ry), vec2(x + rx, y)),
+    ];
+
+    let mut current = vec2(x + rx, y);
+    shape.move_to(current[0], current[1]);
+    for (side, corner) in sides {
+        if side != current {
+            shape.line_to(side[0], side[1]);
+        }
+
+        shape.arc_to(vec2(rx, ry), 0.0, false, true, corner[0], corner[1]);
+        current = corner;
+    }
+
+    shape.close();
+    Ok(shape.into_lines())
+}
+
+fn ellipse(tag: &Tag, rx: f32, ry: f32) -> Result<Vec<Line>, SvgError> {
+    let center = vec2(tag.length("cx")?.unwrap_or(0.0), tag.length("cy")?.unwrap_or(0.0));
+    match rx > 0.0 && ry > 0.0 {
+        true => Ok(vec![Line::Arc { center, radius: vec2(rx, ry), rotation: 0.0, start_angle: 0.0, sweep_angle: TAU }]),
+        false => Ok(Vec::new()),
+    }
+}
+
+/// A `<polygon>` or a `<polyline>`. A filled polyline is closed like a polygon.
+fn polygon(tag: &Tag) -> Result<Vec<Line>, SvgError> {
+    let Some((value, position)) = tag.attribute("points") else {
+        return Ok(Vec::new());
+    };
+
+    let mut parser = Parser { data: value.as_bytes(), position: 0 };
+    let mut points = Vec::new();
+    parser.skip_separators();
+    while parser.position < parser.data.len() {
+        points.push(parser.point().map_err(|_| SvgError::InvalidAttribute { position })?);
+        parser.skip_separators();
+    }
+
+    let mut shape = Shape::new();
+    if let [first, rest @ ..] = points.as_slice() {
+        shape.move_to(first[0], first[1]);
+        for p in rest {
+            shape.line_to(p[0], p[1]);
+        }
+    }
+
+    shape.close();
+    Ok(shape.into_lines())
+}
+
+/// Orient the contours of an element so that it covers the same area with the [FillRule::NonZero] fill rule, with a positive winding.
+fn orient(mut lines: Vec<Line>, even_odd: bool) -> Vec<Line> {
+    if even_odd {
+        let mut shape = Shape::from_lines(&lines);
+        shape.orient_contours();
+        return shape.into_lines();
+    }
+
+    if signed_area(&lines) < 0.0 {
+        reverse_lines(&mut lines);
+    }
+
+    lines
+}
+
+/// Parse the value of a `transform` attribute. Returns `None` if the value is not a list of transform functions.
+fn parse_transform(value: &str) -> Option<Matrix> {
+    let mut parser = Parser { data: value.as_bytes(), position: 0 };
+    let mut matrix = IDENTITY;
+    loop {
+        parser.skip_separators();
+        let start = parser.position;
+        while parser.data.get(parser.position).is_some_and(|c| c.is_ascii_alphabetic()) {
+            parser.position += 1;
+        }
+
+        if parser.position == start {
+            return match start == parser.data.len() {
+                true => Some(matrix),
+                false => None,
+            };
+        }
+
+        let name = &value[start..parser.position];
+        while parser.data.get(parser.position).is_some_and(|c| c.is_ascii_whitespace()) {
+            parser.position += 1;
+        }
+
+        if parser.data.get(parser.position) != Some(&b'(') {
+            return None;
+        }
+
+        parser.position += 1;
+        let mut args = [0.0; 6];
+        let mut count = 0;
+        loop {
+            parser.skip_separators();
+            match parser.data.get(parser.position) {
+                Some(b')') => break,
+                _ if count == args.len() => return None,
+                _ => {
+                    args[count] = parser.number().ok()?;
+                    count += 1;
+                }
+            }
+        }
+
+        parser.position += 1;
+        let [a0, a1, a2, ..] = args;
+        let transform = match (name, count) {
+            ("matrix", 6) => args,
+            ("translate", 1 | 2) => [1.0, 0.0, 0.0, 1.0, a0, a1],
+            ("scale", 1) => [a0, 0.0, 0.0, a0, 0.0, 0.0],
+            ("scale", 2) => [a0, 0.0, 0.0, a1, 0.0, 0.0],
+            ("rotate", 1 | 3) => {
+                // Rotation around the point (a1, a2)
+                let (sin, cos) = a0.to_radians().sin_cos();
+                [cos, sin, -sin, cos, a1 - cos*a1 + sin*a2, a2 - sin*a1 - cos*a2]
+            },
+            ("skewX", 1) => [1.0, 0.0, a0.to_radians().tan(), 1.0, 0.0, 0.0],
+            ("skewY", 1) => [1.0, a0.to_radians().tan(), 0.0, 1.0, 0.0, 0.0],
+            _ => return None,
+        };
+
+        matrix = multiply(matrix, transform);
+    }
+}
+
+/// The transform applying `n`, then `m`
+fn multiply(m: Matrix, n: Matrix) -> Matrix {
+    [
+        m[0]*n[0] + m[2]*n[1],
+        m[1]*n[0] + m[3]*n[1],
+        m[0]*n[2] + m[2]*n[3],
+        m[1]*n[2] + m[3]*n[3],
+        m[0]*n[4] + m[2]*n[5] + m[4],
+        m[1]*n[4] + m[3]*n[5] + m[5],
+    ]
+}
+
+/// The state inherited by the children of an element
+#[derive(Copy, Clone)]
+struct Group<'a> {
+    name: &'a str,
+    /// Transform from the user space of the element to the user space of the root element
+    transform: Matrix,
+    /// Size of the viewport of the nearest `<svg>` element, in its user space
+    viewport: (f32, f32),
+    /// If the element is rendered. Only the children of groups are rendered
+    rendered: bool,
+    /// If the element is filled (the `fill` property is not `none`)
+    fill: bool,
+    /// If the `fill-rule` property is `evenodd`
+    even_odd: bool,
+}
+
+impl<'a> Group<'a> {
+
+    fn child(&self, tag: &Tag<'a>) -> Result<Group<'a>, SvgError> {
+        let transform = match tag.attribute("transform") {
+            Some((value, position)) => multiply(self.transform, parse_transform(value).ok_or(SvgError::InvalidAttribute { position })?),
+            None => self.transform,
+        };
+
+        let fill = match tag.property("fill") {
+            Some("none") => false,
+            Some("inherit") | None => self.fill,
+            Some(_) => true,
+        };
+
+        let even_odd = match tag.property("fill-rule") {
+            Some("evenodd") => true,
+            Some("nonzero") => false,
+            _ => self.even_odd,
+        };
+
+        Ok(Group {
+            name: tag.name,
+            transform,
+            viewport: self.viewport,
+            rendered: self.rendered && matches!(self.name, "svg" | "g" | "a") && tag.property("display") != Some("none"),
+            fill,
+            even_odd,
+        })
+    }
+
+    /// Map the `viewBox` of a nested `<svg>` to the rectangle of its `x`, `y`, `width` and `height` attributes.
+    /// A missing `width` or `height` is the size of the parent viewport.
+    fn nested_viewport(self, tag: &Tag<'a>) -> Result<Group<'a>, SvgError> {
+        let (x, y) = (tag.length("x")?.unwrap_or(0.0), tag.length("y")?.unwrap_or(0.0));
+        let width = tag.length("width")?.unwrap_or(self.viewport.0);
+        let height = tag.length("height")?.unwrap_or(self.viewport.1);
+        let translate = [1.0, 0.0, 0.0, 1.0, x, y];
+
+        let Some((value, position)) = tag.attribute("viewBox") else {
+            return Ok(Group { transform: multiply(self.transform, translate), viewport: (width, height), ..self });
+        };
+
+        let view_box = ViewBox::parse(value).ok_or(SvgError::InvalidAttribute { position })?;
+        let (align, slice) = match tag.attribute("preserveAspectRatio") {
+            Some((value, position)) => parse_aspect_ratio(value).ok_or(SvgError::InvalidAttribute { position })?,
+            None => (Some((0.5, 0.5)), false),
+        };
+
+        let (sx, sy) = (width / view_box.width, height / view_box.height);
+        let (sx, sy, (ax, ay)) = match align {
+            Some(align) if slice => (sx.max(sy), sx.max(sy), align),
+            Some(align) => (sx.min(sy), sx.min(sy), align),
+            None => (sx, sy, (0.0, 0.0)),
+        };
+
+        // Align the scaled view box in the viewport, then move it to `(x, y)`
+        let offset_x = (width - view_box.width * sx) * ax - view_box.x * sx;
+        let offset_y = (height - view_box.height * sy) * ay - view_box.y * sy;
+        let view_box_transform = [sx, 0.0, 0.0, sy, offset_x, offset_y];
+
+        Ok(Group {
+            transform: multiply(self.transform, multiply(translate, view_box_transform)),
+            viewport: (view_box.width, view_box.height),
+            ..self
+        })
+    }
+
+}
+
+/// Parse the value of a `preserveAspectRatio` attribute. Returns the alignment of the view box in the viewport (`None` if the
+/// view box is stretched), and if the view box is scaled to cover the viewport (`slice`) instead of fitting in it (`meet`).
+fn parse_aspect_ratio(value: &str) -> Option<(Option<(f32, f32)>, bool)> {
+    let mut words = value.split_ascii_whitespace().peekable();
+    words.next_if_eq(&"defer");
+
+    let align = match words.next()? {
+        "none" => None,
+        align => {
+            let position = |value: &str, min: &str, mid: &str, max: &str| match value {
+                _ if value == min => Some(0.0),
+                _ if value == mid => Some(0.5),
+                _ if value == max => Some(1.0),
+                _ => None,
+            };
+
+            let (x, y) = (align.get(..4)?, align.get(4..)?);
+            Some((position(x, "xMin", "xMid", "xMax")?, position(y, "YMin", "YMid", "YMax")?))
+        }
+    };
+
+    let slice = match words.next() {
+        Some("meet") | None => false,
+        Some("slice") => true,
+        Some(_) => return None,
+    };
+
+    match words.next() {
+        Some(_) => None,
+        None => Some((align, slice)),
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum TagKind {
+    /// `<name>`
+    Start,
+    /// `</name>`
+    End,
+    /// `<name/>`
+    Empty,
+}
+
+struct Tag<'a> {
+    /// Name of the element, without the namespace prefix
+    name: &'a str,
+    kind: TagKind,
+    /// Byte offset of the tag in the document
+    position: usize,
+    /// Name, value and byte offset of the value of the attributes
+    attributes: Vec<(&'a str, &'a str, usize)>,
+}
+
+impl<'a> Tag<'a> {
+
+    fn attribute(&self, name: &str) -> Option<(&'a str, usize)> {
+        self.attributes.iter()
+            .find(|attribute| attribute.0 == name)
+            .map(|&(_, value, position)| (value, position))
+    }
+
+    /// Value of a presentation attribute. The `style` attribute has priority over the attribute of the same name.
+    fn property(&self, name: &str) -> Option<&'a str> {
+        let style = self.attribute("style").and_then(|(style, _)| {
+            style.split(';')
+                .filter_map(|declaration| declaration.split_once(':'))
+                .rfind(|(property, _)| property.trim() == name)
+        });
+
+        match style {
+            Some((_, value)) => Some(value.trim()),
+            None => self.attribute(name).map(|(value, _)| value.trim()),
+        }
+    }
+
+    /// Value of an attribute that is a length in user units (a number, optionally followed by `px`)
+    fn length(&self, name: &str) -> Result<Option<f32>, SvgError> {
+        let Some((value, position)) = self.attribute(name) else {
+            return Ok(None);
+        };
+
+        let mut parser = Parser { data: value.as_bytes(), position: 0 };
+        let length = parser.number().map_err(|_| SvgError::InvalidAttribute { position })?;
+        match value[parser.position..].trim() {
+            "" | "px" => Ok(Some(length)),
+            _ => Err(SvgError::InvalidAttribute { position }),
+        }
+    }
+
+}
+
+/// Reads the tags of a XML document, skipping the text, the comments and the declarations
+struct Reader<'a> {
+    data: &'a str,
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+
+    fn next_tag(&mut self) -> Result<Option<Tag<'a>>, SvgError> {
+        const SKIPPED: [(&str, &str); 4] = [("<!--", "-->"), ("<![CDATA[", "]]>"), ("<?", "?>"), ("<!", ">")];
+
+        loop {
+            let Some(offset) = self.data[self.position..].find('<') else {
+                return Ok(None);
+            };
+
+            self.position += offset;
+            let rest = &self.data[self.position..];
+            let Some(&(start, end)) = SKIPPED.iter().find(|(start, _)| rest.starts_with(start)) else {
+                return self.tag().map(Some);
+            };
+
+            match rest[start.len()..].find(end) {
+                Some(length) => { self.position += start.len() + length + end.len(); },
+                None => return Err(self.invalid()),
+            }
+        }
+    }
+
+    fn tag(&mut self) -> Result<Tag<'a>, SvgError> {
+        let position = self.position;
+        self.position += 1;
+        let end = self.eat(b'/');
+        let name = self.name();
+        if name.is_empty() {
+            return Err(self.invalid());
+        }
+
+        let mut tag = Tag {
+            name: name.rsplit(':').next().unwrap_or(name),
+            kind: if end { TagKind::End } else { TagKind::Start },
+            position,
+            attributes: Vec::new(),
+        };
+
+        loop {
+            self.skip_whitespace();
+            if self.eat(b'>') {
+                break;
+            }
+
+            if !end && self.data[self.position..].starts_with("/>") {
+                self.position += 2;
+                tag.kind = TagKind::Empty;
+                break;
+            }
+
+            let attribute = self.name();
+            self.skip_whitespace();
+            if end || attribute.is_empty() || !self.eat(b'=') {
+                return Err(self.invalid());
+            }
+
+            self.skip_whitespace();
+            let quote = match self.data.as_bytes().get(self.position) {
+                Some(&quote @ (b'"' | b'\'')) => quote as char,
+                _ => return Err(self.invalid()),
+            };
+
+            let start = self.position + 1;
+            let Some(length) = self.data[start..].find(quote) else {
+                return Err(self.invalid());
+            };
+
+            tag.attributes.push((attribute, &self.data[start..start + length], start));
+            self.position = start + length + 1;
+        }
+
+        Ok(tag)
+    }
+
+    /// Read the name of an element or an attribute
+    fn name(&mut self) -> &'a str {
+        let data = self.data;
+        let start = self.position;
+        while data.as_bytes().get(self.position).is_some_and(|c| !(c.is_ascii_whitespace() || b"<>/='\"".contains(c))) {
+            self.position += 1;
+        }
+
+        &data[start..self.position]
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        match self.data.as_bytes().get(self.position) == Some(&c) {
+            true => {
+                self.position += 1;
+                true
+            },
+            false => false,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.data.as_bytes().get(self.position).is_some_and(|c| c.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn invalid(&self) -> SvgError {
+        SvgError::InvalidDocument { position: self.position }
+    }
+
+}
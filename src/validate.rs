@@ -0,0 +1,127 @@
+//! Validation and cleanup of the lines of a shape.
+use std::borrow::Cow;
+use crate::line::Line;
+use crate::math::Point;
+use crate::shape::{connected, split_contours};
+
+/// A curve is a straight line if its control points are closer than this (relative to the length of the line) to the segment between its end points
+const STRAIGHT_THRESHOLD: f32 = 1e-6;
+
+/// A problem found in the lines of a shape by [validate_lines]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShapeIssue {
+    /// A point of the line at `index` is NaN or infinite. The line is ignored by the generators.
+    NonFinite { index: usize },
+    /// A point of the line at `index` is outside of the normalized range (`0.0` to `1.0`). It will be clipped from the sdf.
+    OutOfRange { index: usize },
+    /// The contour made of the lines `first..=last` does not end where it starts. The pixels inside the shape will be wrong.
+    OpenContour { first: usize, last: usize },
+}
+
+/// Check the lines of a shape before generating its sdf
+///
+/// The lines are split in contours everywhere the end of a line is not the start of the next one, and each contour must be closed.
+///
+/// # Return
+///
+/// Returns every issue found, in the order of the lines. An empty list means the shape is valid.
+///
+/// # Example
+///
+/// ```rust
+/// use easy_signed_distance_field as sdf;
+///
+/// let lines = [
+///     sdf::Line::Line { start: sdf::vec2(0.5, 0.0), end: sdf::vec2(1.0, 1.0) },
+///     sdf::Line::Line { start: sdf::vec2(1.0, 1.0), end: sdf::vec2(0.0, 1.0) },
+/// ];
+///
+/// assert_eq!(sdf::validate_lines(&lines), vec![sdf::ShapeIssue::OpenContour { first: 0, last: 1 }]);
+/// ```
+pub fn validate_lines(lines: &[Line]) -> Vec<ShapeIssue> {
+    let mut issues = Vec::new();
+    let mut first = 0;
+    for contour in split_contours(lines) {
+        for (i, line) in contour.iter().enumerate() {
+            let index = first + i;
+            let points = points(line);
+            if !points.iter().all(|p| p[0].is_finite() && p[1].is_finite()) {
+                issues.push(ShapeIssue::NonFinite { index });
+            } else if !points.iter().all(|p| (0.0..=1.0).contains(&p[0]) && (0.0..=1.0).contains(&p[1])) {
+                issues.push(ShapeIssue::OutOfRange { index });
+            }
+        }
+
+        let last = first + contour.len() - 1;
+        if !connected(contour[contour.len() - 1].end(), contour[0].start()) {
+            issues.push(ShapeIssue::OpenContour { first, last });
+        }
+
+        first = last + 1;
+    }
+
+    issues
+}
+
+/// Remove the lines that cannot be rendered (zero length or non finite coordinates), and replace the curves that are straight by a [Line::Line].
+/// Returns `lines` unchanged if there is nothing to clean.
+pub(crate) fn clean_lines(lines: &[Line]) -> Cow<'_, [Line]> {
+    if lines.iter().all(|line| clean_line(line) == Some(*line)) {
+        return Cow::Borrowed(lines);
+    }
+
+    Cow::Owned(lines.iter().filter_map(clean_line).collect())
+}
+
+fn clean_line(line: &Line) -> Option<Line> {
+    let points = points(line);
+    if !points.iter().all(|p| p[0].is_finite() && p[1].is_finite()) {
+        return None;
+    }
+
+    let start = line.start();
+    if points.iter().all(|&p| p == start) {
+        return None;
+    }
+
+    Some(straight_line(line).unwrap_or(*line))
+}
+
+/// Return the segment between the end points of a quad or a cubic curve if all its control points are on it. Returns `None` for
+/// curved lines, and for lines and arcs.
+pub(crate) fn straight_line(line: &Line) -> Option<Line> {
+    let (start, end) = (line.start(), line.end());
+    match *line {
+        Line::Line { .. } | Line::Arc { .. } => None,
+        _ if points(line).iter().all(|&p| on_segment(p, start, end)) => Some(Line::Line { start, end }),
+        _ => None,
+    }
+}
+
+/// Check if `p` is on the segment between `start` and `end` (with a tolerance of [STRAIGHT_THRESHOLD])
+fn on_segment(p: Point, start: Point, end: Point) -> bool {
+    let ba = end - start;
+    let length = ba.dot(ba);
+    if length == 0.0 {
+        return false;
+    }
+
+    let pa = p - start;
+    let h = pa.dot(ba) / length;
+    let offset = pa - (ba * h.clamp(0.0, 1.0));
+    let distance = offset.dot(offset);
+    distance <= (STRAIGHT_THRESHOLD * STRAIGHT_THRESHOLD) * length
+}
+
+/// Return the points of the line. Lines and quads repeat some of their points. Arcs return their end points and the corners of their bounding box.
+fn points(line: &Line) -> [Point; 4] {
+    match *line {
+        Line::Arc { .. } => {
+            let (min, max) = line.bounds();
+            [line.start(), line.end(), min, max]
+        },
+        Line::Line { start, end } => [start, end, start, end],
+        Line::Quad { start, end, control } => [start, control, end, control],
+        Line::Curve { start, end, first_control, second_control } => [start, first_control, second_control, end],
+    }
+}